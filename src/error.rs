@@ -0,0 +1,446 @@
+use std::{fmt, io, path::PathBuf};
+
+#[derive(Debug)]
+pub enum ScafError {
+    /// the template directory passed on the command line doesn't exist
+    TemplateNotFound(PathBuf),
+    /// cloning or checking out a git template failed
+    Git { url: String, message: String },
+    /// a template archive couldn't be extracted
+    Archive { path: PathBuf, message: String },
+    /// reading or writing a file failed
+    Io { path: PathBuf, source: io::Error },
+    /// a template file is bigger than the size limit
+    FileTooLarge { path: PathBuf, size: u64, max: u64 },
+    /// the template doesn't have a config file
+    ConfigNotFound(PathBuf),
+    /// scaf.toml has bytes that aren't valid utf8, starting at `offset`
+    ConfigNotUtf8 { path: PathBuf, offset: usize },
+    /// scaf.toml couldn't be parsed
+    ConfigParse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    /// the template needs a newer version of scaf
+    TooOld {
+        required: semver::Version,
+        current: semver::Version,
+    },
+    /// scaf.toml parsed, but doesn't make sense
+    InvalidConfig(String),
+    /// a glob pattern couldn't be turned into a regex
+    InvalidGlob {
+        pattern: String,
+        source: regex::Error,
+    },
+    /// the interactive prompt failed
+    Prompt(io::Error),
+    /// the user chose to stop
+    Aborted,
+    /// the user pressed escape in a prompt
+    Cancelled,
+    /// options passed with `--select` that aren't in the config
+    UnknownOptions {
+        unknown: Vec<String>,
+        available: Vec<String>,
+    },
+    /// an option passed with `--ignore-case` that matches more than one option
+    AmbiguousOption { given: String, matches: Vec<String> },
+    /// variables passed with `--set` that aren't in the config
+    UnknownSetVariables(Vec<String>),
+    /// options used in file names that aren't in the config, along with the file they're in
+    UndefinedOptions(Vec<(PathBuf, String)>),
+    /// an alias that ends up standing for itself, in the order they were followed
+    AliasCycle(Vec<String>),
+    /// a lock file passed to `--replay` has options or variables the template doesn't anymore
+    StaleLock { path: PathBuf, unknown: Vec<String> },
+    /// a value for a variable with `choices` that isn't one of them
+    InvalidChoice {
+        variable: String,
+        value: String,
+        choices: Vec<String>,
+    },
+    /// a value for a variable with a `pattern` that doesn't match it. `message` is the variable's
+    /// `pattern_message`, or says what the pattern is
+    PatternMismatch {
+        variable: String,
+        value: String,
+        message: String,
+    },
+    /// variables without a default that weren't given a value, when there's no prompt
+    MissingVariables(Vec<String>),
+    /// chosen options whose requirements weren't chosen, with `add_required = false`. the second
+    /// one is required by the first
+    MissingRequires(Vec<(String, String)>),
+    /// an option that ends up requiring itself, in the order they were followed
+    RequiresCycle(Vec<String>),
+    /// an option that requires more than one option from the same exclusive group
+    ContradictoryRequires {
+        option: String,
+        conflicting: Vec<String>,
+    },
+    /// more than one option from an exclusive group was chosen
+    ExclusiveOptions {
+        group: Vec<String>,
+        chosen: Vec<String>,
+    },
+    /// `{{var}}` tokens that don't have a value, along with the file they're in
+    UnknownVariables(Vec<(PathBuf, String)>),
+    /// a `scaf:if` block in a file's contents that doesn't line up
+    Conditional {
+        path: PathBuf,
+        line: usize,
+        message: String,
+    },
+    /// `{{var|filter}}` tokens with a filter that doesn't exist, along with the file they're in
+    UnknownFilters(Vec<(PathBuf, String)>),
+    /// `{{env:NAME}}` tokens without a default for environment variables that aren't set, along with
+    /// the file they're in
+    MissingEnv(Vec<(PathBuf, String)>),
+    /// files from more than one template going to the same path with `--merge-strategy error`,
+    /// along with the template files for each
+    TemplatesConflict(Vec<(PathBuf, Vec<PathBuf>)>),
+    /// more than one file could be written to the same path, for every path where that happens
+    DedupeConflict {
+        conflicts: Vec<crate::Conflict>,
+        chosen: Vec<String>,
+    },
+    /// files that aren't inside the directory passed to `--strip-prefix`
+    OutsidePrefix {
+        prefix: PathBuf,
+        files: Vec<PathBuf>,
+    },
+    /// paths that would be written outside of the output directory
+    OutsideOutput {
+        output: PathBuf,
+        paths: Vec<PathBuf>,
+    },
+    /// every file in the template depends on an option or value, and none of them were met by what
+    /// was chosen. `files` is how many files the template has, which can be none at all
+    NothingToScaffold { chosen: Vec<String>, files: usize },
+    /// the output directory is the template directory or inside of it
+    OutputInTemplate { template: PathBuf, output: PathBuf },
+    /// the output directory already has something in it
+    OutputNotEmpty(PathBuf),
+    /// several things failed at once, like writing files in parallel
+    Multiple(Vec<ScafError>),
+    /// a hook command couldn't be run or exited unsuccessfully
+    Hook { command: String, message: String },
+    /// the `path_transform` script failed, timed out, or printed nothing for `path`
+    PathTransform {
+        script: PathBuf,
+        path: PathBuf,
+        message: String,
+    },
+    /// `scaf lint` found this many problems
+    Lint(usize),
+    /// files that would be written over, without `--overwrite`
+    FilesExist(Vec<PathBuf>),
+}
+
+impl ScafError {
+    /// ctrl-c in a prompt comes back as an interrupted read, which is the same as cancelling
+    pub fn prompt(e: io::Error) -> Self {
+        if e.kind() == io::ErrorKind::Interrupted {
+            ScafError::Cancelled
+        } else {
+            ScafError::Prompt(e)
+        }
+    }
+
+    pub fn io(path: impl Into<PathBuf>, source: io::Error) -> Self {
+        ScafError::Io {
+            path: path.into(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for ScafError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScafError::TemplateNotFound(path) => {
+                write!(f, "path does not exist: {}", path.display())
+            }
+            ScafError::Git { url, message } => write!(f, "couldn't clone {}: {}", url, message),
+            ScafError::Archive { path, message } => {
+                write!(f, "couldn't extract {}: {}", path.display(), message)
+            }
+            ScafError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            ScafError::FileTooLarge { path, size, max } => write!(
+                f,
+                "{} is {} bytes, over the limit of {} (use --max-file-size to raise it or --skip-large to leave it out)",
+                path.display(),
+                size,
+                max
+            ),
+            ScafError::ConfigNotFound(path) => {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                let dir = path.parent().unwrap_or(path);
+                write!(
+                    f,
+                    "no {} found in {}; is this a scaf template?\nhint: create a {} with an [options] table to make it one",
+                    name,
+                    dir.display(),
+                    name
+                )
+            }
+            ScafError::ConfigNotUtf8 { path, offset } => write!(
+                f,
+                "{} isn't valid utf8, the first invalid byte is at offset {}",
+                path.display(),
+                offset
+            ),
+            ScafError::ConfigParse { path, source } => {
+                write!(f, "couldn't parse {}: {}", path.display(), source)
+            }
+            ScafError::TooOld { required, current } => write!(
+                f,
+                "this template needs scaf {} or newer, but this is scaf {}\nhint: upgrade with `cargo install scaf`",
+                required, current
+            ),
+            ScafError::InvalidConfig(e) => write!(f, "invalid scaf.toml: {}", e),
+            ScafError::InvalidGlob { pattern, source } => {
+                write!(f, "invalid glob `{}`: {}", pattern, source)
+            }
+            ScafError::Prompt(e) => write!(f, "prompt failed: {}", e),
+            ScafError::Aborted => write!(f, "aborted"),
+            ScafError::Cancelled => write!(f, "cancelled"),
+            ScafError::UnknownOptions { unknown, available } => write!(
+                f,
+                "unknown options: {}\navailable options: {}",
+                unknown.join(", "),
+                available.join(", ")
+            ),
+            ScafError::AmbiguousOption { given, matches } => write!(
+                f,
+                "`{}` could be any of these options: {}",
+                given,
+                matches.join(", ")
+            ),
+            ScafError::UnknownSetVariables(unknown) => {
+                write!(
+                    f,
+                    "unknown variables passed to --set: {}",
+                    unknown.join(", ")
+                )
+            }
+            ScafError::AliasCycle(cycle) => {
+                write!(
+                    f,
+                    "aliases can't include themselves: {}",
+                    cycle.join(" -> ")
+                )
+            }
+            ScafError::StaleLock { path, unknown } => write!(
+                f,
+                "{} has options or variables that aren't in the template anymore: {}",
+                path.display(),
+                unknown.join(", ")
+            ),
+            ScafError::InvalidChoice {
+                variable,
+                value,
+                choices,
+            } => write!(
+                f,
+                "`{}` isn't a choice for {}, it has to be one of: {}",
+                value,
+                variable,
+                choices.join(", ")
+            ),
+            ScafError::PatternMismatch {
+                variable,
+                value,
+                message,
+            } => write!(
+                f,
+                "`{}` isn't a valid value for {}: {}",
+                value, variable, message
+            ),
+            ScafError::MissingVariables(missing) => {
+                write!(f, "variables without a value: {}", missing.join(", "))
+            }
+            ScafError::MissingRequires(missing) => {
+                write!(f, "options that need other options to be chosen too:")?;
+                for (option, required) in missing {
+                    write!(f, "\n  `{}` requires `{}`", option, required)?;
+                }
+                Ok(())
+            }
+            ScafError::RequiresCycle(cycle) => {
+                write!(f, "options can't require themselves: {}", cycle.join(" -> "))
+            }
+            ScafError::ContradictoryRequires {
+                option,
+                conflicting,
+            } => write!(
+                f,
+                "`{}` can never be chosen, it requires {} but only one of them can be chosen",
+                option,
+                conflicting.join(" and ")
+            ),
+            ScafError::ExclusiveOptions { group, chosen } => write!(
+                f,
+                "only one of [{}] can be chosen, but {} were",
+                group.join(", "),
+                chosen.join(", ")
+            ),
+            ScafError::UndefinedOptions(undefined) => {
+                write!(f, "options that aren't in scaf.toml:")?;
+                for (path, name) in undefined {
+                    write!(f, "\n  `{}` in {}", name, path.display())?;
+                }
+                Ok(())
+            }
+            ScafError::UnknownVariables(unknown) => {
+                write!(f, "unknown variables:")?;
+                for (path, name) in unknown {
+                    write!(f, "\n  `{{{{{}}}}}` in {}", name, path.display())?;
+                }
+                Ok(())
+            }
+            ScafError::UnknownFilters(unknown) => {
+                write!(
+                    f,
+                    "unknown filters (available: snake, kebab, camel, pascal, upper, lower):"
+                )?;
+                for (path, token) in unknown {
+                    write!(f, "\n  `{}` in {}", token, path.display())?;
+                }
+                Ok(())
+            }
+            ScafError::MissingEnv(missing) => {
+                write!(
+                    f,
+                    "environment variables that aren't set (use `{{{{env:NAME|default}}}}` for a fallback):"
+                )?;
+                for (path, name) in missing {
+                    write!(f, "\n  `{}` in {}", name, path.display())?;
+                }
+                Ok(())
+            }
+            ScafError::Conditional {
+                path,
+                line,
+                message,
+            } => write!(f, "{}:{}: {}", path.display(), line, message),
+            ScafError::TemplatesConflict(conflicts) => {
+                write!(
+                    f,
+                    "more than one template has these files (use --merge-strategy override or concat to combine them):"
+                )?;
+                for (path, sources) in conflicts {
+                    write!(f, "\n  {}:", path.display())?;
+                    for source in sources {
+                        write!(f, "\n    {}", source.display())?;
+                    }
+                }
+                Ok(())
+            }
+            ScafError::DedupeConflict { conflicts, chosen } => {
+                write!(
+                    f,
+                    "can't choose between files with similar options. chosen options: [{}]",
+                    chosen.join(", ")
+                )?;
+                for conflict in conflicts {
+                    write!(f, "\n  {}:", conflict.path.display())?;
+                    for file in &conflict.files {
+                        write!(
+                            f,
+                            "\n    {} depends on [{}]",
+                            file.source.display(),
+                            file.depends_on.join(", ")
+                        )?;
+                    }
+                }
+                Ok(())
+            }
+            ScafError::OutsidePrefix { prefix, files } => {
+                write!(f, "these files aren't inside of {}:", prefix.display())?;
+                for file in files {
+                    write!(f, "\n  {}", file.display())?;
+                }
+                Ok(())
+            }
+            ScafError::OutsideOutput { output, paths } => {
+                write!(
+                    f,
+                    "refusing to write outside of {}, check the template's paths and variables:",
+                    output.display()
+                )?;
+                for path in paths {
+                    write!(f, "\n  {}", path.display())?;
+                }
+                Ok(())
+            }
+            ScafError::NothingToScaffold { files: 0, .. } => {
+                write!(f, "nothing to scaffold, the template doesn't have any files")
+            }
+            ScafError::NothingToScaffold { chosen, .. } if chosen.is_empty() => write!(
+                f,
+                "nothing to scaffold, no options were chosen and every file in the template depends on one\nhint: choose some with --select"
+            ),
+            ScafError::NothingToScaffold { chosen, .. } => write!(
+                f,
+                "nothing to scaffold, the template doesn't have any files without options and none of them match the chosen options: [{}]",
+                chosen.join(", ")
+            ),
+            ScafError::OutputInTemplate { template, output } => write!(
+                f,
+                "the output directory {} can't be inside of the template {}",
+                output.display(),
+                template.display()
+            ),
+            ScafError::OutputNotEmpty(path) => write!(
+                f,
+                "output directory is not empty: {} (use --force to scaffold into it anyway)",
+                path.display()
+            ),
+            ScafError::Multiple(errors) => {
+                write!(f, "{} errors:", errors.len())?;
+                for e in errors {
+                    write!(f, "\n  {}", e)?;
+                }
+                Ok(())
+            }
+            ScafError::Hook { command, message } => {
+                write!(f, "hook `{}` failed: {}", command, message)
+            }
+            ScafError::PathTransform {
+                script,
+                path,
+                message,
+            } => write!(
+                f,
+                "path_transform {} failed for {}: {}",
+                script.display(),
+                path.display(),
+                message
+            ),
+            ScafError::Lint(count) => write!(f, "found {} problems", count),
+            ScafError::FilesExist(paths) => {
+                write!(
+                    f,
+                    "these files already exist (use --overwrite or --overwrite-policy to replace them):"
+                )?;
+                for path in paths {
+                    write!(f, "\n  {}", path.display())?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScafError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ScafError::Io { source, .. } | ScafError::Prompt(source) => Some(source),
+            ScafError::ConfigParse { source, .. } => Some(source),
+            ScafError::InvalidGlob { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+/// runs a filter from `{{name|filter}}` on a variable's value, or `None` if there's no filter with
+/// that name
+pub fn apply(filter: &str, value: &str) -> Option<String> {
+    let converted = match filter {
+        "snake" => words(value).join("_"),
+        "kebab" => words(value).join("-"),
+        "camel" => {
+            let words = words(value);
+            let mut converted = words.first().cloned().unwrap_or_default();
+            converted.extend(words.iter().skip(1).map(|w| capitalize(w)));
+            converted
+        }
+        "pascal" => words(value).iter().map(|w| capitalize(w)).collect(),
+        "upper" => value.to_uppercase(),
+        "lower" => value.to_lowercase(),
+        _ => return None,
+    };
+
+    Some(converted)
+}
+
+/// the lowercased words in something like `My App`, `my-app`, or `myApp`
+fn words(value: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut previous_lower = false;
+
+    for c in value.chars() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            previous_lower = false;
+            continue;
+        }
+
+        // a capital after a lowercase letter starts a new word, like the `A` in `myApp`
+        if c.is_uppercase() && previous_lower && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        previous_lower = c.is_lowercase() || c.is_numeric();
+        word.extend(c.to_lowercase());
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,65 @@
+use regex::Regex;
+
+/// a gitignore style glob, matched against `/` separated paths relative to the template root
+#[derive(Debug, Clone)]
+pub struct Glob {
+    re: Regex,
+    /// patterns ending in `/` only match directories
+    dir_only: bool,
+}
+
+impl Glob {
+    /// `*` and `?` match within a single path segment and `**` matches across segments.
+    /// patterns with a `/` in them are anchored to the root, otherwise they match at any depth.
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        let dir_only = pattern.ends_with('/');
+        let pattern = pattern.trim_end_matches('/');
+        let anchored = pattern.contains('/');
+        let pattern = pattern.trim_start_matches('/');
+
+        let mut re = String::from("^");
+        if !anchored {
+            re.push_str("(?:.*/)?");
+        }
+
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '*' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    if chars.peek() == Some(&'/') {
+                        chars.next();
+                        re.push_str("(?:.*/)?");
+                    } else {
+                        re.push_str(".*");
+                    }
+                }
+                '*' => re.push_str("[^/]*"),
+                '?' => re.push_str("[^/]"),
+                c => re.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+        re.push('$');
+
+        Ok(Glob {
+            re: Regex::new(&re)?,
+            dir_only,
+        })
+    }
+
+    /// like `new`, but always anchored to the root even without a `/` in the pattern
+    pub fn anchored(pattern: &str) -> Result<Self, regex::Error> {
+        Glob::new(&format!("/{}", pattern.trim_start_matches('/')))
+    }
+
+    pub fn matches(&self, path: &str, is_dir: bool) -> bool {
+        (is_dir || !self.dir_only) && self.re.is_match(path)
+    }
+
+    /// whether the file at `path`, or any of the directories it's in, matches
+    pub fn matches_within(&self, path: &str) -> bool {
+        path.match_indices('/')
+            .any(|(i, _)| self.matches(&path[..i], true))
+            || self.matches(path, false)
+    }
+}
@@ -0,0 +1,65 @@
+use std::path::Path;
+
+use console::style;
+
+use scaf::error::ScafError;
+
+use crate::InitArgs;
+
+const CONFIG: &str = r#"# options get picked when the template is scaffolded. a file or directory with `{key}` in its name
+# is only scaffolded when that option is chosen, so `{example}example.md` needs `example`.
+
+# options that start out chosen in the prompt
+# defaults = ["example"]
+
+[options]
+example = { name = "Example", description = "scaffolds example.md" }
+# typescript = "TypeScript"
+
+# values that get asked for and put into files with `{{project_name}}`
+# [variables.project_name]
+# prompt = "Project name"
+# default = "my-app"
+"#;
+
+const EXAMPLE: &str = "this file is only scaffolded when the `example` option is chosen.\n";
+
+const IGNORE: &str = "# gitignore style globs for files in the template that shouldn't be scaffolded\n# node_modules/\n";
+
+pub fn run(args: InitArgs) -> Result<(), ScafError> {
+    let dir = args.dir.as_path();
+    match std::fs::read_dir(dir) {
+        Ok(mut entries) => {
+            if !args.force && entries.next().is_some() {
+                return Err(ScafError::OutputNotEmpty(dir.to_path_buf()));
+            }
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            std::fs::create_dir_all(dir).map_err(|e| ScafError::io(dir, e))?;
+        }
+        Err(e) => return Err(ScafError::io(dir, e)),
+    }
+
+    write(&dir.join("scaf.toml"), CONFIG)?;
+    write(&dir.join("{example}example.md"), EXAMPLE)?;
+    write(&dir.join(".scafignore"), IGNORE)?;
+
+    println!(
+        "{} a template in {}",
+        style("Created").green().bold(),
+        dir.display()
+    );
+    println!("next:");
+    println!(
+        "  add options to {} and files that use them",
+        dir.join("scaf.toml").display()
+    );
+    println!("  scaf lint {}", dir.display());
+    println!("  scaf new {} ./out", dir.display());
+
+    Ok(())
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ScafError> {
+    std::fs::write(path, contents).map_err(|e| ScafError::io(path, e))
+}
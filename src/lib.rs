@@ -0,0 +1,2460 @@
+//! scaffolds projects from a template directory. the binary is a thin wrapper around [`scaffold`],
+//! which can be used on its own without any prompts
+
+pub mod error;
+mod filter;
+mod glob;
+pub mod source;
+
+use console::style;
+use error::ScafError;
+use glob::Glob;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt,
+    io::{Read, Write},
+    path::{Component, Path, PathBuf},
+    process::Stdio,
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+/// what gets picked instead of prompting, for [`scaffold`]
+#[derive(Debug, Clone, Default)]
+pub struct Selections {
+    /// option keys, aliases get expanded
+    pub options: Vec<String>,
+    /// values for the config's `[variables]`. ones that are left out use their default
+    pub variables: HashMap<String, String>,
+}
+
+/// scaffolds the template at `template` into `output` without any prompts, returning the paths that
+/// were written. unlike the binary it doesn't run hooks, and it errors instead of writing over
+/// files that already exist. the template's `path_transform` script does get run
+pub fn scaffold(
+    template: &Path,
+    output: &Path,
+    selections: &Selections,
+) -> Result<Vec<PathBuf>, ScafError> {
+    let config_name = "scaf.toml";
+    let config = Config::from_base(template, config_name)?;
+    check_output_path(template, output)?;
+
+    let (files, _) =
+        read_files_from_path(template, &config, config_name, SizeLimit::default(), false)?;
+    let chosen = validate_selection(&config, &selections.options)?;
+    let chosen = config.expand_aliases(&chosen)?;
+    let chosen = config.apply_requires(&chosen)?;
+    check_exclusive_groups(&config, &chosen)?;
+    let mut values = config.resolve_values(&selections.variables)?;
+    values.extend(config.builtin_values(&chosen));
+
+    let files = render(files, template, output, &chosen, &values, None, &config)?;
+    let existing = files
+        .iter()
+        .filter(|f| f.path.exists())
+        .map(|f| f.path.clone())
+        .collect::<Vec<_>>();
+    if !existing.is_empty() {
+        return Err(ScafError::FilesExist(existing));
+    }
+
+    let mut written = vec![];
+    for f in &files {
+        write_file(f)?;
+        written.push(f.path.clone());
+    }
+    written.sort();
+
+    Ok(written)
+}
+
+/// everything between picking options and writing: keeps the files that go with the chosen
+/// options and values, fills in their contents and paths, and moves them into `output`. the
+/// config's `path_tokens` and `path_transform` decide what happens to the paths
+pub fn render(
+    files: Vec<File>,
+    template: &Path,
+    output: &Path,
+    chosen: &[String],
+    values: &HashMap<String, String>,
+    strip_prefix: Option<&Path>,
+    config: &Config,
+) -> Result<Vec<File>, ScafError> {
+    let mut files = dedupe_files(files, chosen, values)?;
+    apply_conditionals(&mut files, chosen, values)?;
+    interpolate_files(&mut files, values)?;
+    interpolate_paths(&mut files, template, values, config.path_tokens);
+    replace_file_paths(
+        &mut files,
+        template,
+        output,
+        strip_prefix,
+        config.path_transform.as_deref(),
+    )?;
+    // `{var}` tokens can change where files sort
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(files)
+}
+
+/// makes sure the output directory isn't the template or somewhere inside of it, which would write
+/// the scaffolded files back into the template
+pub fn check_output_path(template_path: &Path, output_path: &Path) -> Result<(), ScafError> {
+    let template = template_path
+        .canonicalize()
+        .map_err(|e| ScafError::io(template_path, e))?;
+    let output = canonicalize_missing(output_path)?;
+
+    if output.starts_with(&template) {
+        return Err(ScafError::OutputInTemplate {
+            template: template_path.to_path_buf(),
+            output: output_path.to_path_buf(),
+        });
+    }
+
+    Ok(())
+}
+
+/// canonicalizes the part of `path` that exists and tacks the rest back on, since the output
+/// directory usually hasn't been made yet
+fn canonicalize_missing(path: &Path) -> Result<PathBuf, ScafError> {
+    let mut absolute = PathBuf::new();
+    let cwd = std::env::current_dir().map_err(|e| ScafError::io(path, e))?;
+    // the missing part can't be canonicalized, so `..` has to be taken care of by hand
+    for component in cwd.join(path).components() {
+        match component {
+            Component::ParentDir => {
+                absolute.pop();
+            }
+            Component::CurDir => {}
+            c => absolute.push(c),
+        }
+    }
+
+    let mut existing = absolute.as_path();
+    let mut missing = vec![];
+    loop {
+        match existing.canonicalize() {
+            Ok(canonical) => {
+                return Ok(missing
+                    .into_iter()
+                    .rev()
+                    .fold(canonical, |p: PathBuf, c| p.join(c)))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                match (existing.parent(), existing.file_name()) {
+                    (Some(parent), Some(name)) => {
+                        missing.push(name);
+                        existing = parent;
+                    }
+                    _ => return Err(ScafError::io(path, e)),
+                }
+            }
+            Err(e) => return Err(ScafError::io(path, e)),
+        }
+    }
+}
+
+/// writes the file to a temp file next to it and renames that into place, so a write that gets
+/// interrupted never leaves half a file behind. returns `false` if the rename didn't work and the
+/// file had to be written directly instead
+pub fn write_file(f: &File) -> Result<bool, ScafError> {
+    use std::io::Write;
+
+    // only create directories that actually hold a file, so folders whose files were all
+    // filtered out don't show up in the output. create_dir_all doesn't mind if another thread
+    // already made the directory
+    let parent = f.path.parent().unwrap_or(Path::new("."));
+    std::fs::create_dir_all(parent).map_err(|e| ScafError::io(parent, e))?;
+
+    let mut temp = tempfile::Builder::new()
+        .prefix(".scaf-")
+        .tempfile_in(parent)
+        .map_err(|e| ScafError::io(parent, e))?;
+    temp.write_all(&f.contents)
+        .map_err(|e| ScafError::io(temp.path(), e))?;
+    if let Some(mode) = f.mode {
+        set_file_mode(temp.path(), mode).map_err(|e| ScafError::io(temp.path(), e))?;
+    }
+    if temp.persist(&f.path).is_ok() {
+        return Ok(true);
+    }
+
+    // the temp file gets deleted when the error is dropped
+    std::fs::write(&f.path, &f.contents).map_err(|e| ScafError::io(&f.path, e))?;
+    if let Some(mode) = f.mode {
+        set_file_mode(&f.path, mode).map_err(|e| ScafError::io(&f.path, e))?;
+    }
+
+    Ok(false)
+}
+
+/// moves the files from the template into the output directory, taking `strip_prefix` off of the
+/// front of their paths first and then running them through the `transform` script. errors if any
+/// of them would end up outside of the output directory, like from a variable with `..` in it
+pub fn replace_file_paths(
+    files: &mut [File],
+    template_path: &Path,
+    output_path: &Path,
+    strip_prefix: Option<&Path>,
+    transform: Option<&Path>,
+) -> Result<(), ScafError> {
+    let mut outside = vec![];
+
+    for f in files.iter_mut() {
+        let mut stripped = f
+            .path
+            .strip_prefix(template_path)
+            .expect("prefix is the same");
+
+        if let Some(prefix) = strip_prefix {
+            match stripped.strip_prefix(prefix) {
+                Ok(s) => stripped = s,
+                Err(_) => {
+                    outside.push(f.source.clone());
+                    continue;
+                }
+            }
+        }
+
+        f.path = match transform {
+            Some(script) => output_path.join(transform_path(script, stripped)?),
+            None => output_path.join(stripped),
+        };
+    }
+
+    if let Some(prefix) = strip_prefix {
+        if !outside.is_empty() {
+            outside.sort();
+            return Err(ScafError::OutsidePrefix {
+                prefix: prefix.to_path_buf(),
+                files: outside,
+            });
+        }
+    }
+
+    let mut escaping = vec![];
+    for f in files.iter() {
+        if escapes_output(output_path, &f.path)? {
+            escaping.push(f.path.clone());
+        }
+    }
+    if !escaping.is_empty() {
+        escaping.sort();
+        return Err(ScafError::OutsideOutput {
+            output: output_path.to_path_buf(),
+            paths: escaping,
+        });
+    }
+
+    Ok(())
+}
+
+/// how long a `path_transform` script gets for each path before it's killed
+const PATH_TRANSFORM_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// runs `script` with `path` on stdin, `/` separated and followed by a newline, and returns the
+/// path it prints. it has to exit successfully within `PATH_TRANSFORM_TIMEOUT`
+fn transform_path(script: &Path, path: &Path) -> Result<PathBuf, ScafError> {
+    let error = |message: String| ScafError::PathTransform {
+        script: script.to_path_buf(),
+        path: path.to_path_buf(),
+        message,
+    };
+
+    let mut child = std::process::Command::new(script)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| error(e.to_string()))?;
+    let input = format!("{}\n", relative_path(Path::new(""), path));
+    if let Some(mut stdin) = child.stdin.take() {
+        // a script that doesn't read its input is fine, so a closed pipe isn't an error
+        let _ = stdin.write_all(input.as_bytes());
+    }
+    // read while the script runs, since one that prints more than the pipe holds would be stuck
+    // waiting for it to be read otherwise
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let reader = std::thread::spawn(move || {
+        let mut output = Vec::new();
+        stdout.read_to_end(&mut output).map(|_| output)
+    });
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(|e| error(e.to_string()))? {
+            break status;
+        }
+        if started.elapsed() > PATH_TRANSFORM_TIMEOUT {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(error(format!(
+                "it took longer than {}s",
+                PATH_TRANSFORM_TIMEOUT.as_secs()
+            )));
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    };
+    if !status.success() {
+        return Err(error(status.to_string()));
+    }
+
+    let output = reader
+        .join()
+        .expect("reading stdout doesn't panic")
+        .map_err(|e| error(e.to_string()))?;
+    let output = String::from_utf8_lossy(&output);
+    let transformed = output.trim();
+    if transformed.is_empty() {
+        return Err(error(String::from("it didn't print a path")));
+    }
+
+    Ok(PathBuf::from(transformed))
+}
+
+/// whether `path` ends up somewhere outside of `output_path` once `..` and symlinks are resolved
+pub fn escapes_output(output_path: &Path, path: &Path) -> Result<bool, ScafError> {
+    let output = canonicalize_missing(output_path)?;
+    Ok(!canonicalize_missing(path)?.starts_with(output))
+}
+
+/// how big a template file can be, so a huge file that ended up in the template by mistake doesn't
+/// get read into memory
+#[derive(Debug, Clone, Copy)]
+pub struct SizeLimit {
+    pub max_bytes: u64,
+    /// leave files that are too big out instead of erroring
+    pub skip: bool,
+}
+
+impl Default for SizeLimit {
+    fn default() -> Self {
+        SizeLimit {
+            max_bytes: 10 * 1024 * 1024,
+            skip: false,
+        }
+    }
+}
+
+/// a template file that was left out for being bigger than the `SizeLimit`
+#[derive(Debug, Clone)]
+pub struct LargeFile {
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+/// every file in the template, along with the ones that were skipped for being bigger than
+/// `limit` and how big they are. hidden files are left out unless `include_hidden` is set
+pub fn read_files_from_path(
+    path: &Path,
+    config: &Config,
+    config_name: &str,
+    limit: SizeLimit,
+    include_hidden: bool,
+) -> Result<(Vec<File>, Vec<LargeFile>), ScafError> {
+    let attach = config.attach_globs()?;
+    let raw = config.raw_globs()?;
+    let listed = config
+        .listed_files
+        .iter()
+        .filter(|l| l.template == path)
+        .collect::<Vec<_>>();
+    let mut relatives = match config.is_scanned(path) {
+        true => walk_template(path, config_name, include_hidden)?.0,
+        false => vec![],
+    };
+    // listed files go where `[files]` says instead, and the path_transform script is part of the
+    // template, not the output
+    relatives.retain(|r| {
+        let source = path.join(r);
+        !listed.iter().any(|l| l.source == source)
+            && config.path_transform.as_ref() != Some(&source)
+    });
+
+    let mut files = vec![];
+    let mut skipped = vec![];
+    for relative in &relatives {
+        let source = path.join(relative);
+        if let Some(large) = too_large(&source, limit)? {
+            skipped.push(large);
+            continue;
+        }
+
+        let options = dependencies(relative, config, &attach);
+        let mut file = create_file(path, relative, options, config)?;
+        file.raw = raw.iter().any(|g| g.matches_within(relative));
+        files.push(file);
+    }
+    for l in listed {
+        if let Some(large) = too_large(&l.source, limit)? {
+            skipped.push(large);
+            continue;
+        }
+
+        let mut file = create_listed_file(path, l)?;
+        file.raw = raw.iter().any(|g| g.matches_within(&l.from));
+        files.push(file);
+    }
+    skipped.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok((files, skipped))
+}
+
+/// the file's size if it's over the limit and `skip` is set. without `skip` that's an error
+fn too_large(source: &Path, limit: SizeLimit) -> Result<Option<LargeFile>, ScafError> {
+    let size = std::fs::metadata(source)
+        .map_err(|e| ScafError::io(source, e))?
+        .len();
+    if size <= limit.max_bytes {
+        return Ok(None);
+    }
+    if !limit.skip {
+        return Err(ScafError::FileTooLarge {
+            path: source.to_path_buf(),
+            size,
+            max: limit.max_bytes,
+        });
+    }
+
+    Ok(Some(LargeFile {
+        path: source.to_path_buf(),
+        size,
+    }))
+}
+
+/// like `read_files_from_path`, for several templates scaffolded together. every file's path is put
+/// inside of the first template so files from different templates can be deduped against each
+/// other
+pub fn read_files_from_paths(
+    paths: &[&Path],
+    config: &Config,
+    config_name: &str,
+    limit: SizeLimit,
+    include_hidden: bool,
+) -> Result<(Vec<File>, Vec<LargeFile>), ScafError> {
+    let mut files = vec![];
+    let mut skipped = vec![];
+    for (layer, path) in paths.iter().enumerate() {
+        let (read, large) = read_files_from_path(path, config, config_name, limit, include_hidden)?;
+        files.extend(read.into_iter().map(|mut f| {
+            let relative = f.path.strip_prefix(path).expect("read from this template");
+            f.path = paths[0].join(relative);
+            f.layer = layer;
+            f
+        }));
+        skipped.extend(large);
+    }
+
+    Ok((files, skipped))
+}
+
+/// every directory in the template that should be in the output for the chosen options, even if
+/// it doesn't end up with any files in it. used for `--keep-empty-dirs`
+pub fn read_dirs_from_path(
+    path: &Path,
+    config: &Config,
+    config_name: &str,
+    chosen: &[String],
+    values: &HashMap<String, String>,
+    include_hidden: bool,
+) -> Result<Vec<PathBuf>, ScafError> {
+    if !config.is_scanned(path) {
+        return Ok(vec![]);
+    }
+    let attach = config.attach_globs()?;
+    let (_, dirs) = walk_template(path, config_name, include_hidden)?;
+
+    Ok(dirs
+        .iter()
+        .filter(|relative| {
+            dependencies(relative, config, &attach)
+                .iter()
+                .all(|d| d.is_met(chosen, values))
+        })
+        .map(|relative| path.join(normalize_path(&strip_options(relative, config), config)))
+        .collect())
+}
+
+/// dotfiles that get scaffolded even without `include_hidden`, since templates usually want them in
+/// the output
+const HIDDEN_ALLOWLIST: &[&str] = &[
+    ".gitignore",
+    ".gitattributes",
+    ".gitkeep",
+    ".github",
+    ".editorconfig",
+];
+
+/// whether a file or directory starts with a `.` and isn't in the `HIDDEN_ALLOWLIST`, like
+/// `.DS_Store` or an editor's swap files
+fn is_hidden(path: &Path) -> bool {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    name.starts_with('.') && !HIDDEN_ALLOWLIST.contains(&name.as_ref())
+}
+
+/// the relative paths of all the files and directories in the template that aren't ignored
+fn walk_template(
+    path: &Path,
+    config_name: &str,
+    include_hidden: bool,
+) -> Result<(Vec<String>, Vec<String>), ScafError> {
+    let ignore = read_ignore_file(path)?;
+    let mut files = vec![];
+    let mut walked_dirs = vec![];
+    // stack of directories left to walk, so nested template folders are picked up too
+    let mut dirs = vec![path.to_path_buf()];
+
+    while let Some(dir) = dirs.pop() {
+        let entries = std::fs::read_dir(&dir).map_err(|e| ScafError::io(&dir, e))?;
+        for entry in entries {
+            let entry_path = entry.map_err(|e| ScafError::io(&dir, e))?.path();
+            let relative = relative_path(path, &entry_path);
+            let is_dir = entry_path.is_dir();
+
+            if ignore.iter().any(|g| g.matches(&relative, is_dir)) {
+                continue;
+            }
+            if !include_hidden && is_hidden(&entry_path) {
+                continue;
+            }
+
+            if is_dir {
+                dirs.push(entry_path);
+                walked_dirs.push(relative);
+                continue;
+            }
+
+            if relative == config_name || relative == ".scafignore" {
+                continue;
+            }
+            // configs in subdirectories get merged into the main one
+            if dir != path && entry_path.file_name() == Path::new(config_name).file_name() {
+                continue;
+            }
+
+            files.push(relative);
+        }
+    }
+    // read_dir's order depends on the filesystem
+    files.sort();
+    walked_dirs.sort();
+
+    Ok((files, walked_dirs))
+}
+
+/// the options in the path, plus any that are attached by a glob in the config
+fn dependencies(relative: &str, config: &Config, attach: &[(String, Glob)]) -> Vec<Dependency> {
+    let mut options = options_in_file(relative, config);
+    for (option, glob) in attach {
+        let dependency = Dependency::Option(option.clone());
+        if glob.matches_within(relative) && !options.contains(&dependency) {
+            options.push(dependency);
+        }
+    }
+
+    options
+}
+
+/// the path of a template file relative to the template root, always `/` separated so it can be
+/// matched against globs
+pub fn relative_path(base: &Path, path: &Path) -> String {
+    path.strip_prefix(base)
+        .expect("walked from the template root")
+        .components()
+        // pain
+        .map(|c| c.as_os_str().to_str().expect("normal string"))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// reads the globs in `.scafignore` at the template root, skipping comments and blank lines
+fn read_ignore_file(base: &Path) -> Result<Vec<Glob>, ScafError> {
+    let ignore_path = base.join(".scafignore");
+    let contents = match std::fs::read_to_string(&ignore_path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(ScafError::io(&ignore_path, e)),
+    };
+
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| {
+            Glob::new(l).map_err(|source| ScafError::InvalidGlob {
+                pattern: String::from(l),
+                source,
+            })
+        })
+        .collect()
+}
+
+/// how option and variable tokens are written in file names, for when braces are awkward to type or
+/// mean something else to a tool
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PathTokens {
+    /// `{ts}`
+    #[default]
+    Braces,
+    /// `[[ts]]`
+    Brackets,
+    /// `__ts__`
+    Underscores,
+}
+
+/// how file names get normalized in the output, for templates with inconsistently named files
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NormalizeFilenames {
+    /// `MyFile.TXT` to `myfile.txt`
+    Lower,
+    /// `MyFile.TXT` to `my-file.txt`
+    Kebab,
+}
+
+impl NormalizeFilenames {
+    fn apply(self, name: &str) -> String {
+        match self {
+            NormalizeFilenames::Lower => name.to_lowercase(),
+            // each part between dots is its own name, so extensions stay and dotfiles keep their
+            // dot. anything like `_` in front is kept too, since it usually means something
+            NormalizeFilenames::Kebab => name
+                .split('.')
+                .map(|part| {
+                    let rest = part.trim_start_matches(|c: char| !c.is_alphanumeric());
+                    let prefix = &part[..part.len() - rest.len()];
+                    let kebab = filter::apply("kebab", rest).expect("kebab is a filter");
+                    format!("{}{}", prefix, kebab)
+                })
+                .collect::<Vec<_>>()
+                .join("."),
+        }
+    }
+}
+
+impl PathTokens {
+    /// matches a single token. tokens can't have their delimiters or a `/` in them, so each one
+    /// stays inside of one path segment and several can sit next to each other
+    fn regex(self) -> &'static Regex {
+        static BRACES: OnceLock<Regex> = OnceLock::new();
+        static BRACKETS: OnceLock<Regex> = OnceLock::new();
+        static UNDERSCORES: OnceLock<Regex> = OnceLock::new();
+
+        let (lock, pattern) = match self {
+            PathTokens::Braces => (&BRACES, r"\{[^{}/]+\}"),
+            PathTokens::Brackets => (&BRACKETS, r"\[\[[^\[\]/]+\]\]"),
+            // lazy so `__a____b__` is two tokens, while `__use_ts__` is still one
+            PathTokens::Underscores => (&UNDERSCORES, r"__[^/]+?__"),
+        };
+        lock.get_or_init(|| Regex::new(pattern).expect("valid regex"))
+    }
+
+    /// what's inside of a token
+    fn name(self, token: &str) -> &str {
+        let (open, close) = match self {
+            PathTokens::Braces => ("{", "}"),
+            PathTokens::Brackets => ("[[", "]]"),
+            PathTokens::Underscores => ("__", "__"),
+        };
+        token
+            .strip_prefix(open)
+            .and_then(|t| t.strip_suffix(close))
+            .unwrap_or(token)
+            .trim()
+    }
+}
+
+/// `{var}` tokens for variables aren't options, they get replaced with the variable's value later
+pub fn options_in_file(path: &str, config: &Config) -> Vec<Dependency> {
+    let tokens = config.path_tokens;
+    let caps = tokens
+        .regex()
+        .find_iter(path)
+        .map(|m| tokens.name(m.as_str()))
+        .collect::<Vec<_>>();
+
+    // sorted so the same options in a different order end up as the same `depends_on`
+    let mut options = BTreeSet::new();
+
+    for c in caps.into_iter().filter(|c| !config.is_variable(c)) {
+        options.extend(parse_dependencies(c));
+    }
+
+    options.into_iter().collect()
+}
+
+/// the dependencies in something like `a,b|c,!d,e=f`
+pub fn parse_dependencies(s: &str) -> impl Iterator<Item = Dependency> + '_ {
+    s.split(',').map(str::trim).map(|o| {
+        if o.contains('|') {
+            let mut options = o
+                .split('|')
+                .map(|o| String::from(o.trim()))
+                .collect::<Vec<_>>();
+            options.sort();
+            options.dedup();
+            Dependency::AnyOf(options)
+        } else if let Some(o) = o.strip_prefix('!') {
+            Dependency::Not(String::from(o.trim()))
+        } else if let Some((name, value)) = o.split_once('=') {
+            Dependency::Value {
+                name: String::from(name.trim()),
+                value: String::from(value.trim()),
+            }
+        } else {
+            Dependency::Option(String::from(o))
+        }
+    })
+}
+
+pub fn create_file(
+    base: &Path,
+    relative: &str,
+    options: Vec<Dependency>,
+    config: &Config,
+) -> Result<File, ScafError> {
+    let source = base.join(relative);
+    let contents = std::fs::read(&source).map_err(|e| ScafError::io(&source, e))?;
+    let metadata = std::fs::metadata(&source).map_err(|e| ScafError::io(&source, e))?;
+
+    // renamed files go exactly where the rename says
+    let stripped = strip_options(relative, config);
+    let path = match config.renamed(stripped.clone()) {
+        renamed if renamed != stripped => renamed,
+        _ => normalize_path(&stripped, config),
+    };
+
+    Ok(File {
+        path: base.join(path),
+        source,
+        contents,
+        depends_on: options,
+        mode: file_mode(&metadata),
+        layer: 0,
+        raw: false,
+    })
+}
+
+/// a file from `[files]`, which goes exactly where it's listed to go
+fn create_listed_file(base: &Path, listed: &ListedFile) -> Result<File, ScafError> {
+    let source = &listed.source;
+    let contents = std::fs::read(source).map_err(|e| ScafError::io(source, e))?;
+    let metadata = std::fs::metadata(source).map_err(|e| ScafError::io(source, e))?;
+
+    Ok(File {
+        path: base.join(&listed.to),
+        source: source.clone(),
+        contents,
+        depends_on: listed.depends_on.clone(),
+        mode: file_mode(&metadata),
+        layer: 0,
+        raw: false,
+    })
+}
+
+#[cfg(unix)]
+fn file_mode(metadata: &std::fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(metadata.permissions().mode())
+}
+
+/// there's no mode to carry over outside of unix, so files just get the default permissions
+#[cfg(not(unix))]
+fn file_mode(_metadata: &std::fs::Metadata) -> Option<u32> {
+    None
+}
+
+#[cfg(unix)]
+fn set_file_mode(path: &Path, mode: u32) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))
+}
+
+#[cfg(not(unix))]
+fn set_file_mode(_path: &Path, _mode: u32) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// removes the `{option}` tokens from every component of the path, dropping any directories
+/// whose name was only an option. `{var}` tokens are left for `interpolate_paths`
+pub fn strip_options(path: &str, config: &Config) -> PathBuf {
+    let tokens = config.path_tokens;
+    Path::new(path)
+        .components()
+        .map(|c| {
+            tokens.regex().replace_all(
+                c.as_os_str().to_str().expect("normal string"),
+                |caps: &regex::Captures| {
+                    let token = &caps[0];
+                    if config.is_variable(tokens.name(token)) {
+                        String::from(token)
+                    } else {
+                        String::new()
+                    }
+                },
+            )
+        })
+        .filter(|c| !c.is_empty())
+        .map(|c| c.into_owned())
+        .collect()
+}
+
+/// applies `normalize_filenames` to every component of a path with its options stripped. `{var}`
+/// tokens are left alone, so variable names and the values that replace them keep their case
+pub fn normalize_path(path: &Path, config: &Config) -> PathBuf {
+    let Some(normalize) = config.normalize_filenames else {
+        return path.to_path_buf();
+    };
+    let tokens = config.path_tokens;
+
+    path.components()
+        .map(|c| {
+            let name = c.as_os_str().to_string_lossy();
+            let mut normalized = String::new();
+            let mut last = 0;
+            for m in tokens.regex().find_iter(&name) {
+                normalized.push_str(&normalize.apply(&name[last..m.start()]));
+                normalized.push_str(m.as_str());
+                last = m.end();
+            }
+            normalized.push_str(&normalize.apply(&name[last..]));
+            normalized
+        })
+        .collect()
+}
+
+/// replaces `{var}` tokens in the paths with the variable's value, leaving the template root alone
+pub fn interpolate_paths(
+    files: &mut [File],
+    base: &Path,
+    values: &HashMap<String, String>,
+    tokens: PathTokens,
+) {
+    for f in files {
+        let relative = relative_path(base, &f.path);
+        f.path = base.join(interpolate_path(&relative, values, tokens));
+    }
+}
+
+pub fn interpolate_path(
+    relative: &str,
+    values: &HashMap<String, String>,
+    tokens: PathTokens,
+) -> String {
+    tokens
+        .regex()
+        .replace_all(relative, |caps: &regex::Captures| {
+            let token = &caps[0];
+            match values.get(tokens.name(token)) {
+                Some(value) => value.clone(),
+                None => String::from(token),
+            }
+        })
+        .into_owned()
+}
+
+static VAR_RE: OnceLock<Regex> = OnceLock::new();
+
+/// replaces every `{{var}}` in the file contents with its value, erroring if a file uses a
+/// variable that doesn't exist
+fn var_re() -> &'static Regex {
+    VAR_RE.get_or_init(|| Regex::new(r"\{\{\s*(.+?)\s*\}\}").expect("valid regex"))
+}
+
+/// the names of all the `{{var}}` tokens in a file, leaving out `{{env:...}}`. binary files don't
+/// have any
+pub fn variables_in_contents(f: &File) -> Vec<&str> {
+    if f.raw {
+        return vec![];
+    }
+    match std::str::from_utf8(&f.contents) {
+        Ok(text) => var_re()
+            .captures_iter(text)
+            .map(|c| c.get(1).expect("regex has a group").as_str())
+            .filter(|name| !name.starts_with(ENV_PREFIX))
+            // `{{name|snake}}` is still the variable `name`
+            .map(|name| name.split('|').next().unwrap_or_default().trim())
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
+static DIRECTIVE_RE: OnceLock<Regex> = OnceLock::new();
+
+/// a line that's only a `scaf:if`, `scaf:else`, or `scaf:endif`, after whatever starts a comment
+/// in the file's language. the rest of the line is the condition
+fn directive_re() -> &'static Regex {
+    DIRECTIVE_RE.get_or_init(|| {
+        Regex::new(r"^\s*[^\w\s]*\s*scaf:(if|else|endif)\b(.*)").expect("valid regex")
+    })
+}
+
+/// the condition after a `scaf:if`, without whatever closes the comment, like `-->` or `*/`
+fn directive_condition(rest: &str) -> &str {
+    let rest = rest.trim();
+    if !rest.chars().any(char::is_alphanumeric) {
+        return "";
+    }
+    match rest.rsplit_once(char::is_whitespace) {
+        Some((condition, closer)) if !closer.chars().any(char::is_alphanumeric) => {
+            condition.trim_end()
+        }
+        _ => rest,
+    }
+}
+
+/// keeps or drops the lines between `scaf:if`, `scaf:else`, and `scaf:endif` depending on the
+/// chosen options. the directive lines are always removed
+pub fn apply_conditionals(
+    files: &mut [File],
+    chosen: &[String],
+    values: &HashMap<String, String>,
+) -> Result<(), ScafError> {
+    for f in files.iter_mut().filter(|f| !f.raw) {
+        let Ok(text) = std::str::from_utf8(&f.contents) else {
+            continue;
+        };
+        if !text.contains("scaf:") {
+            continue;
+        }
+
+        let error = |line: usize, message: &str| ScafError::Conditional {
+            path: f.source.clone(),
+            line,
+            message: String::from(message),
+        };
+
+        // one entry per open block: the line it started on, whether it's being kept, and whether
+        // it's past the `scaf:else`
+        let mut blocks: Vec<(usize, bool, bool)> = vec![];
+        let kept = |blocks: &[(usize, bool, bool)]| blocks.iter().all(|&(_, keep, _)| keep);
+        let mut contents = String::with_capacity(text.len());
+
+        for (i, line) in text.split_inclusive('\n').enumerate() {
+            let line_number = i + 1;
+            let Some(caps) = directive_re().captures(line) else {
+                if kept(&blocks) {
+                    contents.push_str(line);
+                }
+                continue;
+            };
+
+            match &caps[1] {
+                "if" => {
+                    let condition = directive_condition(&caps[2]);
+                    if condition.is_empty() {
+                        return Err(error(line_number, "`scaf:if` needs options to check"));
+                    }
+                    let dependencies = parse_dependencies(condition).collect::<Vec<_>>();
+                    // `a b` is probably a missing comma, and an option named that could never match
+                    if dependencies
+                        .iter()
+                        .any(|d| d.to_string().contains(char::is_whitespace))
+                    {
+                        return Err(error(
+                            line_number,
+                            "`scaf:if` options have to be separated with `,`, like `scaf:if a, b`",
+                        ));
+                    }
+                    let met = dependencies.iter().all(|d| d.is_met(chosen, values));
+                    blocks.push((line_number, met, false));
+                }
+                "else" => match blocks.last_mut() {
+                    Some((_, _, true)) => {
+                        return Err(error(
+                            line_number,
+                            "a `scaf:if` can only have one `scaf:else`",
+                        ))
+                    }
+                    Some((_, keep, in_else)) => {
+                        *keep = !*keep;
+                        *in_else = true;
+                    }
+                    None => return Err(error(line_number, "`scaf:else` without a `scaf:if`")),
+                },
+                _ => {
+                    if blocks.pop().is_none() {
+                        return Err(error(line_number, "`scaf:endif` without a `scaf:if`"));
+                    }
+                }
+            }
+        }
+
+        if let Some(&(line_number, _, _)) = blocks.last() {
+            return Err(error(
+                line_number,
+                "`scaf:if` is never closed with a `scaf:endif`",
+            ));
+        }
+
+        f.contents = contents.into_bytes();
+    }
+
+    Ok(())
+}
+
+/// `{{env:NAME}}` is replaced with an environment variable instead of a variable from the config
+const ENV_PREFIX: &str = "env:";
+
+/// variables that scaf fills in itself. configs can't have values or variables starting with
+/// `scaf.`, so there's room for more of these
+const BUILTIN_PREFIX: &str = "scaf.";
+const BUILTINS: &[&str] = &["scaf.options", "scaf.options.json"];
+
+/// the value of an `{{env:NAME}}` or `{{env:NAME|default}}` token, if there is one
+fn env_value(token: &str) -> Option<String> {
+    let (name, default) = match token.split_once('|') {
+        Some((name, default)) => (name.trim(), Some(default.trim())),
+        None => (token, None),
+    };
+
+    std::env::var(name)
+        .ok()
+        .or_else(|| default.map(String::from))
+}
+
+pub fn interpolate_files(
+    files: &mut [File],
+    values: &HashMap<String, String>,
+) -> Result<(), ScafError> {
+    let re = var_re();
+    let mut unknown = vec![];
+    let mut missing_env = vec![];
+    let mut unknown_filters = vec![];
+
+    for f in files.iter_mut().filter(|f| !f.raw) {
+        // anything that isn't utf8 is treated as a binary file and copied as is
+        let Ok(text) = std::str::from_utf8(&f.contents) else {
+            continue;
+        };
+
+        let contents = re.replace_all(text, |caps: &regex::Captures| {
+            let name = &caps[1];
+            if let Some(env) = name.strip_prefix(ENV_PREFIX) {
+                return match env_value(env) {
+                    Some(value) => value,
+                    None => {
+                        missing_env.push((f.path.clone(), String::from(env)));
+                        String::from(&caps[0])
+                    }
+                };
+            }
+
+            let mut filters = name.split('|').map(str::trim);
+            let name = filters.next().unwrap_or_default();
+            let Some(value) = values.get(name) else {
+                unknown.push((f.path.clone(), String::from(name)));
+                return String::from(&caps[0]);
+            };
+
+            let mut value = value.clone();
+            for filter in filters {
+                match filter::apply(filter, &value) {
+                    Some(filtered) => value = filtered,
+                    None => {
+                        unknown_filters.push((f.path.clone(), String::from(&caps[0])));
+                        return String::from(&caps[0]);
+                    }
+                }
+            }
+            value
+        });
+        f.contents = contents.into_owned().into_bytes();
+    }
+
+    if !unknown.is_empty() {
+        return Err(ScafError::UnknownVariables(unknown));
+    }
+    if !missing_env.is_empty() {
+        return Err(ScafError::MissingEnv(missing_env));
+    }
+    if !unknown_filters.is_empty() {
+        return Err(ScafError::UnknownFilters(unknown_filters));
+    }
+
+    Ok(())
+}
+
+/// makes sure at most one option from each of the config's exclusive groups was chosen
+pub fn check_exclusive_groups(config: &Config, chosen: &[String]) -> Result<(), ScafError> {
+    for group in &config.exclusive_groups {
+        let picked = group
+            .iter()
+            .filter(|o| chosen.contains(o))
+            .cloned()
+            .collect::<Vec<_>>();
+        if picked.len() > 1 {
+            return Err(ScafError::ExclusiveOptions {
+                group: group.clone(),
+                chosen: picked,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// every option used by a file that isn't in the config, along with the file using it
+pub fn undefined_options(config: &Config, files: &[File]) -> Vec<(PathBuf, String)> {
+    let mut undefined = vec![];
+    for f in files {
+        for d in &f.depends_on {
+            for o in d.options() {
+                if !config.options.contains_key(o) || !config.in_scope(o, &f.source) {
+                    undefined.push((f.source.clone(), String::from(o)));
+                }
+            }
+            if let Some(name) = d.variable() {
+                if !config.is_variable(name) {
+                    undefined.push((f.source.clone(), String::from(name)));
+                }
+            }
+        }
+    }
+
+    undefined
+}
+
+/// `selected` with every key swapped for the option it matches when case is ignored, like
+/// `TypeScript` for `typescript`. exact matches win, keys that match more than one option are an
+/// error, and ones that don't match any are left for `validate_selection` to report
+pub fn match_ignoring_case(config: &Config, selected: &[String]) -> Result<Vec<String>, ScafError> {
+    let all = config.get_all_options();
+    selected
+        .iter()
+        .map(|s| {
+            if all.contains(s) {
+                return Ok(s.clone());
+            }
+            let matches = all
+                .iter()
+                .filter(|o| o.to_lowercase() == s.to_lowercase())
+                .cloned()
+                .collect::<Vec<_>>();
+            match matches.len() {
+                0 => Ok(s.clone()),
+                1 => Ok(matches[0].clone()),
+                _ => Err(ScafError::AmbiguousOption {
+                    given: s.clone(),
+                    matches,
+                }),
+            }
+        })
+        .collect()
+}
+
+/// checks that every option passed on the command line exists in the config
+pub fn validate_selection(config: &Config, selected: &[String]) -> Result<Vec<String>, ScafError> {
+    // `--select ''` chooses nothing, without prompting
+    let selected = selected
+        .iter()
+        .filter(|s| !s.is_empty())
+        .cloned()
+        .collect::<Vec<_>>();
+    let all = config.get_all_options();
+    let unknown = selected
+        .iter()
+        .filter(|s| !all.contains(s))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if !unknown.is_empty() {
+        let mut available = all;
+        available.sort();
+        return Err(ScafError::UnknownOptions { unknown, available });
+    }
+
+    Ok(selected)
+}
+
+/// the files whose dependencies are met, with only the most specific one for each path from each
+/// template. they're sorted by path and then template, and every path with a tie is reported at
+/// once, in order. files from different templates at the same path are all kept, for
+/// `merge_templates` to combine
+pub fn dedupe_files(
+    files: Vec<File>,
+    chosen: &[String],
+    values: &HashMap<String, String>,
+) -> Result<Vec<File>, ScafError> {
+    let files: Vec<_> = files
+        .into_iter()
+        // first filter out all the ones that don't depend on any of the chosen options
+        .filter(|f| unmet_dependencies(f, chosen, values).is_empty())
+        .collect();
+
+    // group the files by where they'll end up, so duplicates can be resolved in one pass
+    let mut by_path: BTreeMap<PathBuf, Vec<File>> = BTreeMap::new();
+    for f in files {
+        by_path.entry(f.path.clone()).or_default().push(f);
+    }
+
+    let mut deduped_files = vec![];
+    let mut conflicts = vec![];
+    for (path, dups) in by_path {
+        let mut by_layer: BTreeMap<usize, Vec<File>> = BTreeMap::new();
+        for f in dups {
+            by_layer.entry(f.layer).or_default().push(f);
+        }
+        for mut dups in by_layer.into_values() {
+            let dups_ref = dups.iter().collect::<Vec<_>>();
+            match most_specific(&dups_ref) {
+                Some(winner) => deduped_files.push(dups.swap_remove(winner)),
+                None => conflicts.push(Conflict::between(path.clone(), &dups_ref)),
+            }
+        }
+    }
+
+    if !conflicts.is_empty() {
+        let mut chosen = chosen.to_vec();
+        chosen.sort();
+        return Err(ScafError::DedupeConflict { conflicts, chosen });
+    }
+
+    Ok(deduped_files)
+}
+
+/// what happens when more than one template has a file going to the same path
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeStrategy {
+    /// the file from the later template wins
+    Override,
+    /// the files are joined in template order, with a newline between them
+    Concat,
+    /// it's an error, so nothing gets replaced by accident
+    #[default]
+    Error,
+}
+
+/// combines files from different templates that go to the same path, like the ones `dedupe_files`
+/// keeps. the result is sorted by path
+pub fn merge_templates(
+    mut files: Vec<File>,
+    strategy: MergeStrategy,
+) -> Result<Vec<File>, ScafError> {
+    // paths can change after deduping, so later templates might not be right after earlier ones
+    files.sort_by(|a, b| a.path.cmp(&b.path).then(a.layer.cmp(&b.layer)));
+    let mut merged: Vec<File> = vec![];
+    let mut conflicts: Vec<(PathBuf, Vec<PathBuf>)> = vec![];
+    for f in files {
+        let Some(last) = merged.last_mut().filter(|last| last.path == f.path) else {
+            merged.push(f);
+            continue;
+        };
+
+        match strategy {
+            MergeStrategy::Override => *last = f,
+            MergeStrategy::Concat => {
+                if !last.contents.is_empty() && !last.contents.ends_with(b"\n") {
+                    last.contents.push(b'\n');
+                }
+                last.contents.extend(f.contents);
+                last.source = f.source;
+                last.mode = f.mode;
+            }
+            MergeStrategy::Error => match conflicts.last_mut() {
+                Some((path, sources)) if *path == f.path => sources.push(f.source),
+                _ => conflicts.push((f.path.clone(), vec![last.source.clone(), f.source])),
+            },
+        }
+    }
+
+    if !conflicts.is_empty() {
+        return Err(ScafError::TemplatesConflict(conflicts));
+    }
+
+    Ok(merged)
+}
+
+/// the dependencies of `f` that aren't met by the chosen options and values, which leave it out of
+/// the output if there are any
+pub fn unmet_dependencies<'a>(
+    f: &'a File,
+    chosen: &[String],
+    values: &HashMap<String, String>,
+) -> Vec<&'a Dependency> {
+    f.depends_on
+        .iter()
+        .filter(|d| !d.is_met(chosen, values))
+        .collect()
+}
+
+/// files going to the same path that are all as specific as each other, so there's no way to
+/// choose between them
+#[derive(Debug, Clone, Serialize)]
+pub struct Conflict {
+    pub path: PathBuf,
+    pub files: Vec<ConflictingFile>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConflictingFile {
+    /// the template file
+    pub source: PathBuf,
+    pub depends_on: Vec<String>,
+}
+
+impl Conflict {
+    /// the conflict between the most specific of `files`, which all go to `path`
+    pub fn between(path: PathBuf, files: &[&File]) -> Self {
+        let max = files.iter().map(|f| f.depends_on.len()).max().unwrap_or(0);
+        Conflict {
+            path,
+            files: files
+                .iter()
+                .filter(|f| f.depends_on.len() == max)
+                .map(|f| ConflictingFile {
+                    source: f.source.clone(),
+                    depends_on: f.depends_on.iter().map(Dependency::to_string).collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// out of files going to the same path, the one that depends on the most options is the most
+/// specific one, so it wins. `None` if there's a tie
+pub fn most_specific(files: &[&File]) -> Option<usize> {
+    let max = files.iter().map(|f| f.depends_on.len()).max()?;
+    if files.iter().filter(|f| f.depends_on.len() == max).count() > 1 {
+        return None;
+    }
+
+    files.iter().position(|f| f.depends_on.len() == max)
+}
+
+/// a file that could be scaffolded. two files are equal, and hash the same, when they'd be written
+/// to the same `path`, whatever is in them, since only one of them can end up there
+#[derive(Debug, Clone)]
+pub struct File {
+    pub path: PathBuf,
+    /// the template file this was read from
+    pub source: PathBuf,
+    /// raw bytes so binary files make it through untouched
+    pub contents: Vec<u8>,
+    pub depends_on: Vec<Dependency>,
+    /// the unix permissions of the template file, so things like executable scripts stay executable
+    pub mode: Option<u32>,
+    /// which template this came from when several are scaffolded together, later ones win
+    pub layer: usize,
+    /// matched by `raw_globs`, so the contents are copied without any `{{var}}`s being replaced
+    pub raw: bool,
+}
+
+impl PartialEq for File {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path
+    }
+}
+
+impl Eq for File {}
+
+impl std::hash::Hash for File {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.path.hash(state);
+    }
+}
+
+/// something that has to be true about the chosen options for a file to be scaffolded
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dependency {
+    /// `{a}`, the option has to be chosen
+    Option(String),
+    /// `{a|b}`, at least one of the options has to be chosen
+    AnyOf(Vec<String>),
+    /// `{!a}`, the option must not be chosen
+    Not(String),
+    /// `{a=b}`, the variable `a` has to be `b`
+    Value { name: String, value: String },
+}
+
+impl fmt::Display for Dependency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Dependency::Option(o) => write!(f, "{}", o),
+            Dependency::AnyOf(options) => write!(f, "{}", options.join("|")),
+            Dependency::Not(o) => write!(f, "!{}", o),
+            Dependency::Value { name, value } => write!(f, "{}={}", name, value),
+        }
+    }
+}
+
+impl Dependency {
+    /// every option this refers to
+    pub fn options(&self) -> Vec<&str> {
+        match self {
+            Dependency::Option(o) | Dependency::Not(o) => vec![o],
+            Dependency::AnyOf(options) => options.iter().map(String::as_str).collect(),
+            Dependency::Value { .. } => vec![],
+        }
+    }
+
+    /// the variable this refers to, if it's a `{a=b}`
+    pub fn variable(&self) -> Option<&str> {
+        match self {
+            Dependency::Value { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+
+    pub fn is_met(&self, chosen: &[String], values: &HashMap<String, String>) -> bool {
+        match self {
+            Dependency::Option(o) => chosen.contains(o),
+            Dependency::AnyOf(options) => options.iter().any(|o| chosen.contains(o)),
+            Dependency::Not(o) => !chosen.contains(o),
+            Dependency::Value { name, value } => values.get(name) == Some(value),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Config {
+    /// the options that scaf should give the user, key: variable name, value: human readable name
+    /// and description
+    #[serde(default)]
+    pub options: HashMap<String, Opt>,
+    /// options written as `[[option]]` tables, which get moved into `options` when the config is
+    /// loaded
+    #[serde(default, rename = "option")]
+    option_tables: Vec<OptionTable>,
+    /// shown above the prompt
+    #[serde(default)]
+    pub meta: Meta,
+    /// options that start out selected in the prompt
+    #[serde(default)]
+    pub defaults: Vec<String>,
+    /// options that go first in the prompt, in this order. the rest are sorted by name after them
+    #[serde(default)]
+    pub order: Vec<String>,
+    /// globs relative to the template root, every file they match depends on the option,
+    /// key: option, value: glob
+    #[serde(default)]
+    pub attach: HashMap<String, String>,
+    /// groups of options where only one can be chosen at a time, like package managers
+    #[serde(default)]
+    pub exclusive_groups: Vec<Vec<String>>,
+    /// whether options that the chosen ones require get chosen automatically, instead of erroring
+    #[serde(default = "default_true")]
+    pub add_required: bool,
+    /// choosing the key chooses all of the options in the value too, key: option, value: the
+    /// options it stands for, which can be aliases themselves
+    #[serde(default)]
+    pub aliases: HashMap<String, Vec<String>>,
+    /// values that can be put into file contents with `{{key}}`, key: variable name, value: what it's replaced with
+    #[serde(default)]
+    pub values: HashMap<String, String>,
+    /// values that the user gets asked for, which can be used in file contents just like `values`
+    #[serde(default)]
+    pub variables: HashMap<String, Variable>,
+    /// shell commands run in the output directory before any files are written
+    #[serde(default)]
+    pub pre_hooks: Vec<String>,
+    /// shell commands run in the output directory after all the files are written
+    #[serde(default)]
+    pub post_hooks: Vec<String>,
+    /// gitignore style globs for files whose contents are copied as is, like templates that the
+    /// scaffolded project uses itself. their paths still get option tokens stripped
+    #[serde(default)]
+    pub raw_globs: Vec<String>,
+    /// `lower` or `kebab` to change the case of every file and directory name in the output, after
+    /// the option tokens are taken out
+    #[serde(default)]
+    pub normalize_filenames: Option<NormalizeFilenames>,
+    /// an executable, relative to the template root, that gets every output path on stdin and prints
+    /// where it should go instead. joined to the template root when the config is loaded
+    #[serde(default)]
+    pub path_transform: Option<PathBuf>,
+    /// how options and variables are written in file names, `braces` for `{ts}` by default,
+    /// `brackets` for `[[ts]]`, or `underscores` for `__ts__`
+    #[serde(default)]
+    pub path_tokens: PathTokens,
+    /// files that get a different name in the output, like `gitignore` to `.gitignore`, since some
+    /// tools leave dotfiles out. keys with a `/` are paths relative to the template root, the rest
+    /// match file names anywhere. option tokens are stripped before matching
+    #[serde(default)]
+    pub rename: HashMap<String, String>,
+    /// options that came from a config in a subdirectory, key: option, value: the subdirectory
+    #[serde(skip)]
+    pub scopes: HashMap<String, PathBuf>,
+    /// files listed by hand, which get moved into `listed_files` when the config is loaded.
+    /// key: the file relative to the template root, which can be outside of it, value: where it
+    /// goes in the output, or a table with `to` and `depends_on`
+    #[serde(default, rename = "files")]
+    file_table: HashMap<String, ListedFileRepr>,
+    /// the files from `[files]`, sorted by where they come from
+    #[serde(skip)]
+    pub listed_files: Vec<ListedFile>,
+    /// whether the template directory is walked for files. defaults to `true`, or `false` when
+    /// there's a `[files]` table, so listing files replaces walking unless this is set
+    scan: Option<bool>,
+    /// templates that aren't walked for files, since their config turned `scan` off
+    #[serde(skip)]
+    unscanned: Vec<PathBuf>,
+}
+
+/// a file from `[files]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListedFile {
+    /// the key it was listed under
+    pub from: String,
+    /// `from` joined to the template root
+    pub source: PathBuf,
+    /// relative to the output
+    pub to: String,
+    pub depends_on: Vec<Dependency>,
+    /// the template whose config listed it
+    pub template: PathBuf,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum ListedFileRepr {
+    To(String),
+    Full {
+        to: String,
+        #[serde(default)]
+        depends_on: Vec<String>,
+    },
+}
+
+/// adds `option` and everything `edges` leads to from it to `expanded`. `trail` is the options that
+/// led to `option`, so one that ends up leading back to itself can be returned as the cycle
+fn follow<'a>(
+    option: &str,
+    edges: &dyn Fn(&str) -> Option<&'a Vec<String>>,
+    trail: &mut Vec<String>,
+    expanded: &mut Vec<String>,
+) -> Result<(), Vec<String>> {
+    if let Some(start) = trail.iter().position(|o| o == option) {
+        let mut cycle = trail[start..].to_vec();
+        cycle.push(String::from(option));
+        return Err(cycle);
+    }
+
+    if !expanded.iter().any(|o| o == option) {
+        expanded.push(String::from(option));
+    }
+
+    if let Some(next) = edges(option) {
+        trail.push(String::from(option));
+        for o in next {
+            follow(o, edges, trail, expanded)?;
+        }
+        trail.pop();
+    }
+
+    Ok(())
+}
+
+/// reads a config file, which has to be utf8 like any toml file
+fn read_config(path: &Path) -> Result<String, ScafError> {
+    let bytes = std::fs::read(path).map_err(|e| ScafError::io(path, e))?;
+    String::from_utf8(bytes).map_err(|e| ScafError::ConfigNotUtf8 {
+        path: path.to_path_buf(),
+        offset: e.utf8_error().valid_up_to(),
+    })
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// a config in a subdirectory of the template, which can only add options
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct NestedConfig {
+    #[serde(default)]
+    options: HashMap<String, Opt>,
+    #[serde(default, rename = "option")]
+    option_tables: Vec<OptionTable>,
+    #[serde(default)]
+    defaults: Vec<String>,
+}
+
+/// an option written as an `[[option]]` table with its key inside, which has room for everything
+/// an option can have without a long inline table. `default = true` starts it out selected
+#[derive(Deserialize, Debug)]
+struct OptionTable {
+    key: String,
+    name: String,
+    description: Option<String>,
+    #[serde(default)]
+    default: bool,
+    #[serde(default)]
+    hidden: bool,
+    #[serde(default)]
+    requires: Vec<String>,
+    group: Option<String>,
+}
+
+/// moves `tables` into `options`, and the ones that start out selected into `defaults`
+fn add_option_tables(
+    tables: Vec<OptionTable>,
+    options: &mut HashMap<String, Opt>,
+    defaults: &mut Vec<String>,
+) -> Result<(), ScafError> {
+    for table in tables {
+        if options.contains_key(&table.key) {
+            return Err(ScafError::InvalidConfig(format!(
+                "option `{}` is defined more than once",
+                table.key
+            )));
+        }
+        if table.default && !defaults.contains(&table.key) {
+            defaults.push(table.key.clone());
+        }
+        options.insert(
+            table.key,
+            Opt {
+                name: table.name,
+                description: table.description,
+                hidden: table.hidden,
+                requires: table.requires,
+                group: table.group,
+            },
+        );
+    }
+
+    Ok(())
+}
+
+/// an option that can be a plain name, `ts = "TypeScript"`, or a table with a description too,
+/// `ts = { name = "TypeScript", description = "..." }`
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(from = "OptRepr")]
+pub struct Opt {
+    /// human readable name
+    pub name: String,
+    pub description: Option<String>,
+    /// left out of the prompt, but can still be chosen with `--select`. mostly useful for aliases
+    pub hidden: bool,
+    /// other options that have to be chosen along with this one
+    pub requires: Vec<String>,
+    /// heading the option is shown under in the prompt, like "Linting"
+    pub group: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OptRepr {
+    Name(String),
+    Full {
+        name: String,
+        description: Option<String>,
+        #[serde(default)]
+        hidden: bool,
+        #[serde(default)]
+        requires: Vec<String>,
+        group: Option<String>,
+    },
+}
+
+impl From<OptRepr> for Opt {
+    fn from(repr: OptRepr) -> Self {
+        match repr {
+            OptRepr::Name(name) => Opt {
+                name,
+                description: None,
+                hidden: false,
+                requires: vec![],
+                group: None,
+            },
+            OptRepr::Full {
+                name,
+                description,
+                hidden,
+                requires,
+                group,
+            } => Opt {
+                name,
+                description,
+                hidden,
+                requires,
+                group,
+            },
+        }
+    }
+}
+
+impl Opt {
+    /// what gets shown in the prompt. `{{name}}` in the description is replaced with its value from
+    /// `values`, and it's wrapped so no line is wider than `width`. lines after the first start
+    /// with `indent` spaces, and newlines in the description are kept
+    pub fn label(&self, values: &HashMap<String, String>, width: usize, indent: usize) -> String {
+        let Some(description) = &self.description else {
+            return self.name.clone();
+        };
+        let description = var_re().replace_all(description, |caps: &regex::Captures| {
+            values
+                .get(&caps[1])
+                .cloned()
+                .unwrap_or_else(|| String::from(&caps[0]))
+        });
+
+        let mut lines = vec![];
+        let mut line = String::new();
+        // the first line starts after the name
+        let mut line_width = console::measure_text_width(&self.name) + 1;
+        for (i, paragraph) in description.trim().lines().enumerate() {
+            if i > 0 {
+                lines.push(std::mem::take(&mut line));
+                line_width = indent;
+            }
+            for word in paragraph.split_whitespace() {
+                let word_width = console::measure_text_width(word);
+                // a word that's too long for any line still gets one to itself
+                if !line.is_empty() {
+                    if line_width + 1 + word_width > width {
+                        lines.push(std::mem::take(&mut line));
+                        line_width = indent;
+                    } else {
+                        line.push(' ');
+                        line_width += 1;
+                    }
+                }
+                line.push_str(word);
+                line_width += word_width;
+            }
+        }
+        lines.push(line);
+
+        let padding = " ".repeat(indent);
+        let mut label = format!("{} {}", self.name, style(&lines[0]).dim());
+        for line in &lines[1..] {
+            label.push_str(&format!("\n{}{}", padding, style(line).dim()));
+        }
+        label
+    }
+}
+
+/// just the part of the config that has to be read before everything else
+#[derive(Deserialize, Debug)]
+struct VersionCheck {
+    /// the oldest version of scaf that can use the template
+    min_scaf_version: Option<String>,
+}
+
+/// errors if this version of scaf is older than `min`
+fn check_version(min: &str) -> Result<(), ScafError> {
+    let required = semver::Version::parse(min).map_err(|e| {
+        ScafError::InvalidConfig(format!("min_scaf_version `{}` isn't a version: {}", min, e))
+    })?;
+    let current =
+        semver::Version::parse(env!("CARGO_PKG_VERSION")).expect("crate version is valid semver");
+
+    if current < required {
+        return Err(ScafError::TooOld { required, current });
+    }
+
+    Ok(())
+}
+
+/// what the template is, for the `[meta]` table
+#[derive(Deserialize, Debug, Default)]
+pub struct Meta {
+    pub title: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Variable {
+    /// what the user gets asked
+    pub prompt: String,
+    /// prefilled in the prompt. `git:user.name` is looked up in git's config instead
+    pub default: Option<String>,
+    /// the only values it can have, picked from a list instead of typed in
+    #[serde(default)]
+    pub choices: Vec<String>,
+    /// a regex the whole value has to match, like `[a-z][a-z0-9_]*`
+    pub pattern: Option<String>,
+    /// shown instead of the pattern when a value doesn't match it
+    pub pattern_message: Option<String>,
+}
+
+/// `default = "git:user.email"` is filled in from `git config` instead of used as is
+const GIT_CONFIG_PREFIX: &str = "git:";
+
+impl Variable {
+    /// the default with `git:KEY` looked up with `git config --get`, which is empty if git isn't
+    /// installed or doesn't have the key set
+    pub fn default_value(&self) -> Option<String> {
+        let default = self.default.as_ref()?;
+        let Some(key) = default.strip_prefix(GIT_CONFIG_PREFIX) else {
+            return Some(default.clone());
+        };
+
+        let value = std::process::Command::new("git")
+            .args(["config", "--get", key])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+        Some(value.unwrap_or_default())
+    }
+
+    /// the pattern anchored at both ends, so it has to match all of the value
+    fn pattern_regex(&self) -> Option<Result<Regex, regex::Error>> {
+        let pattern = self.pattern.as_ref()?;
+        Some(Regex::new(&format!("^(?:{})$", pattern)))
+    }
+
+    /// what's wrong with `value` if it doesn't match the variable's `pattern`
+    pub fn pattern_mismatch(&self, value: &str) -> Option<String> {
+        let re = self
+            .pattern_regex()?
+            .expect("patterns are checked when the config is loaded");
+        if re.is_match(value) {
+            return None;
+        }
+        Some(match &self.pattern_message {
+            Some(message) => message.clone(),
+            None => format!(
+                "it has to match `{}`",
+                self.pattern.as_deref().unwrap_or_default()
+            ),
+        })
+    }
+}
+
+impl Config {
+    pub fn from_base(base_path: &Path, name: &str) -> Result<Self, ScafError> {
+        Config::load(base_path, name, true)
+    }
+
+    /// like `from_base`, but configs in subdirectories are ignored instead of merged in. they're
+    /// still left out of the scaffolded files
+    pub fn from_root(base_path: &Path, name: &str) -> Result<Self, ScafError> {
+        Config::load(base_path, name, false)
+    }
+
+    fn load(base_path: &Path, name: &str, nested: bool) -> Result<Self, ScafError> {
+        let config_file_path = base_path.join(name);
+        let config_file = read_config(&config_file_path).map_err(|e| match e {
+            ScafError::Io { source, .. } if source.kind() == std::io::ErrorKind::NotFound => {
+                ScafError::ConfigNotFound(config_file_path.clone())
+            }
+            e => e,
+        })?;
+
+        // checked before the rest of the config, which might use things this version can't parse
+        let version = toml::from_str::<VersionCheck>(&config_file).map_err(|source| {
+            ScafError::ConfigParse {
+                path: config_file_path.clone(),
+                source,
+            }
+        })?;
+        if let Some(min) = version.min_scaf_version {
+            check_version(&min)?;
+        }
+
+        let mut config =
+            toml::from_str::<Config>(&config_file).map_err(|source| ScafError::ConfigParse {
+                path: config_file_path.clone(),
+                source,
+            })?;
+        add_option_tables(
+            std::mem::take(&mut config.option_tables),
+            &mut config.options,
+            &mut config.defaults,
+        )?;
+        config.list_files(base_path)?;
+        if let Some(script) = config.path_transform.take() {
+            let script = base_path.join(script);
+            if !script.is_file() {
+                return Err(ScafError::InvalidConfig(format!(
+                    "path_transform {} doesn't exist",
+                    script.display()
+                )));
+            }
+            config.path_transform = Some(script);
+        }
+        if nested {
+            config.merge_nested(base_path, name)?;
+        }
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    /// moves `[files]` into `listed_files`, making sure the files are there and go somewhere inside
+    /// of the output
+    fn list_files(&mut self, base: &Path) -> Result<(), ScafError> {
+        if !self.scan.unwrap_or(self.file_table.is_empty()) {
+            self.unscanned.push(base.to_path_buf());
+        }
+
+        let mut missing = vec![];
+        let mut outside = vec![];
+        for (from, repr) in std::mem::take(&mut self.file_table) {
+            let (to, depends_on) = match repr {
+                ListedFileRepr::To(to) => (to, vec![]),
+                ListedFileRepr::Full { to, depends_on } => (to, depends_on),
+            };
+            let source = base.join(&from);
+            if !source.is_file() {
+                missing.push(source.display().to_string());
+            }
+            if to.is_empty()
+                || !Path::new(&to)
+                    .components()
+                    .all(|c| matches!(c, Component::Normal(_)))
+            {
+                outside.push(from.clone());
+            }
+
+            // sorted so it's the same as the `depends_on` of a file with the options in its name
+            let depends_on = depends_on
+                .iter()
+                .flat_map(|d| parse_dependencies(d))
+                .collect::<BTreeSet<_>>();
+            self.listed_files.push(ListedFile {
+                from,
+                source,
+                to,
+                depends_on: depends_on.into_iter().collect(),
+                template: base.to_path_buf(),
+            });
+        }
+        missing.sort();
+        outside.sort();
+        self.listed_files.sort_by(|a, b| a.from.cmp(&b.from));
+
+        if !missing.is_empty() {
+            return Err(ScafError::InvalidConfig(format!(
+                "files in [files] that don't exist: {}",
+                missing.join(", ")
+            )));
+        }
+        if !outside.is_empty() {
+            return Err(ScafError::InvalidConfig(format!(
+                "files in [files] have to go to relative paths without `..`: {}",
+                outside.join(", ")
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// whether the files in `template` are found by walking it
+    pub fn is_scanned(&self, template: &Path) -> bool {
+        !self.unscanned.iter().any(|t| t == template)
+    }
+
+    /// merges in the options from configs in subdirectories, which only apply to the files in
+    /// that subdirectory. the same option can't be in more than one config
+    fn merge_nested(&mut self, base: &Path, name: &str) -> Result<(), ScafError> {
+        let Some(file_name) = Path::new(name).file_name() else {
+            return Ok(());
+        };
+        // configs in hidden directories get merged too, since whether those are read isn't
+        // known yet
+        let (_, mut dirs) = walk_template(base, name, true)?;
+        dirs.sort();
+
+        for dir in dirs {
+            let path = base.join(&dir).join(file_name);
+            if !path.is_file() {
+                continue;
+            }
+
+            let contents = read_config(&path)?;
+            let mut nested = toml::from_str::<NestedConfig>(&contents).map_err(|source| {
+                ScafError::ConfigParse {
+                    path: path.clone(),
+                    source,
+                }
+            })?;
+            add_option_tables(
+                nested.option_tables,
+                &mut nested.options,
+                &mut nested.defaults,
+            )?;
+
+            let mut options = nested.options.into_iter().collect::<Vec<_>>();
+            options.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (key, opt) in options {
+                if self.options.contains_key(&key) {
+                    let other = match self.scopes.get(&key) {
+                        Some(scope) => scope.join(file_name),
+                        None => base.join(name),
+                    };
+                    return Err(ScafError::InvalidConfig(format!(
+                        "option `{}` is in both {} and {}",
+                        key,
+                        other.display(),
+                        path.display()
+                    )));
+                }
+                self.scopes.insert(key.clone(), base.join(&dir));
+                self.options.insert(key, opt);
+            }
+            self.defaults.extend(nested.defaults);
+        }
+
+        Ok(())
+    }
+
+    /// adds another template's config to this one, for scaffolding several templates together. the
+    /// same option can be in both as long as it's the same in both, and for everything else the
+    /// other config wins or goes after this one
+    pub fn merge(&mut self, other: Config) -> Result<(), ScafError> {
+        let mut conflicting = other
+            .options
+            .iter()
+            .filter(|(key, opt)| self.options.get(*key).is_some_and(|o| o != *opt))
+            .map(|(key, _)| key.as_str())
+            .collect::<Vec<_>>();
+        conflicting.sort();
+        if !conflicting.is_empty() {
+            return Err(ScafError::InvalidConfig(format!(
+                "options that are different in more than one template: {}",
+                conflicting.join(", ")
+            )));
+        }
+        // every template's files are read with the same config
+        if self.path_tokens != other.path_tokens {
+            return Err(ScafError::InvalidConfig(String::from(
+                "templates that use different path_tokens can't be combined",
+            )));
+        }
+
+        self.options.extend(other.options);
+        self.meta.title = self.meta.title.take().or(other.meta.title);
+        self.meta.description = self.meta.description.take().or(other.meta.description);
+        for option in other.defaults {
+            if !self.defaults.contains(&option) {
+                self.defaults.push(option);
+            }
+        }
+        for option in other.order {
+            if !self.order.contains(&option) {
+                self.order.push(option);
+            }
+        }
+        self.attach.extend(other.attach);
+        self.exclusive_groups.extend(other.exclusive_groups);
+        self.add_required &= other.add_required;
+        self.aliases.extend(other.aliases);
+        self.values.extend(other.values);
+        self.variables.extend(other.variables);
+        self.pre_hooks.extend(other.pre_hooks);
+        self.post_hooks.extend(other.post_hooks);
+        self.rename.extend(other.rename);
+        self.raw_globs.extend(other.raw_globs);
+        self.normalize_filenames = other.normalize_filenames.or(self.normalize_filenames);
+        self.path_transform = other.path_transform.or(self.path_transform.take());
+        self.scopes.extend(other.scopes);
+        self.listed_files.extend(other.listed_files);
+        self.unscanned.extend(other.unscanned);
+
+        self.validate()
+    }
+
+    /// whether a template file at `path` can use `option`. options from configs in subdirectories
+    /// can only be used inside of that subdirectory
+    pub fn in_scope(&self, option: &str, path: &Path) -> bool {
+        self.scopes
+            .get(option)
+            .is_none_or(|scope| path.starts_with(scope))
+    }
+
+    /// checks the parts of the config that serde can't
+    fn validate(&self) -> Result<(), ScafError> {
+        let unknown = self
+            .defaults
+            .iter()
+            .filter(|d| !self.options.contains_key(*d))
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        if !unknown.is_empty() {
+            return Err(ScafError::InvalidConfig(format!(
+                "unknown options in defaults: {}",
+                unknown.join(", ")
+            )));
+        }
+
+        let unknown = self
+            .order
+            .iter()
+            .filter(|o| !self.options.contains_key(*o))
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        if !unknown.is_empty() {
+            return Err(ScafError::InvalidConfig(format!(
+                "unknown options in order: {}",
+                unknown.join(", ")
+            )));
+        }
+
+        let mut unknown = self
+            .attach
+            .keys()
+            .filter(|o| !self.options.contains_key(*o))
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        unknown.sort();
+        if !unknown.is_empty() {
+            return Err(ScafError::InvalidConfig(format!(
+                "unknown options in attach: {}",
+                unknown.join(", ")
+            )));
+        }
+
+        let unknown = self
+            .exclusive_groups
+            .iter()
+            .flatten()
+            .filter(|o| !self.options.contains_key(*o))
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        if !unknown.is_empty() {
+            return Err(ScafError::InvalidConfig(format!(
+                "unknown options in exclusive_groups: {}",
+                unknown.join(", ")
+            )));
+        }
+
+        let mut unknown = self
+            .aliases
+            .iter()
+            .flat_map(|(alias, members)| std::iter::once(alias).chain(members))
+            .filter(|o| !self.options.contains_key(*o))
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        unknown.sort();
+        unknown.dedup();
+        if !unknown.is_empty() {
+            return Err(ScafError::InvalidConfig(format!(
+                "unknown options in aliases: {}",
+                unknown.join(", ")
+            )));
+        }
+        // catch cycles even if the alias wouldn't have been chosen
+        let mut aliases = self.aliases.keys().cloned().collect::<Vec<_>>();
+        aliases.sort();
+        self.expand_aliases(&aliases)?;
+
+        let mut unknown = self
+            .options
+            .values()
+            .flat_map(|o| &o.requires)
+            .filter(|o| !self.options.contains_key(*o))
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        unknown.sort();
+        unknown.dedup();
+        if !unknown.is_empty() {
+            return Err(ScafError::InvalidConfig(format!(
+                "unknown options in requires: {}",
+                unknown.join(", ")
+            )));
+        }
+        let mut reserved = self
+            .values
+            .keys()
+            .chain(self.variables.keys())
+            .filter(|k| k.starts_with(BUILTIN_PREFIX))
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        reserved.sort();
+        if !reserved.is_empty() {
+            return Err(ScafError::InvalidConfig(format!(
+                "names starting with `{}` are saved for scaf's own variables: {}",
+                BUILTIN_PREFIX,
+                reserved.join(", ")
+            )));
+        }
+
+        let mut keys = self.variables.keys().collect::<Vec<_>>();
+        keys.sort();
+        for key in keys {
+            let variable = &self.variables[key];
+            if let Some(Err(e)) = variable.pattern_regex() {
+                return Err(ScafError::InvalidConfig(format!(
+                    "variable `{}` has an invalid pattern: {}",
+                    key, e
+                )));
+            }
+            // defaults from git can't be known until they're used
+            if let Some(default) = &variable.default {
+                if !default.starts_with(GIT_CONFIG_PREFIX) {
+                    self.check_value(key, default)?;
+                }
+            }
+        }
+
+        let mut invalid = self
+            .rename
+            .iter()
+            .filter(|(_, to)| {
+                to.is_empty()
+                    || !Path::new(to)
+                        .components()
+                        .all(|c| matches!(c, Component::Normal(_)))
+            })
+            .map(|(from, _)| from.as_str())
+            .collect::<Vec<_>>();
+        invalid.sort();
+        if !invalid.is_empty() {
+            return Err(ScafError::InvalidConfig(format!(
+                "renames have to be relative paths without `..`: {}",
+                invalid.join(", ")
+            )));
+        }
+
+        // an option whose requirements break an exclusive group could never be chosen
+        let mut options = self.options.keys().cloned().collect::<Vec<_>>();
+        options.sort();
+        for option in options {
+            let required = self.requirements(std::slice::from_ref(&option))?;
+            for group in &self.exclusive_groups {
+                let conflicting = group
+                    .iter()
+                    .filter(|o| required.contains(o))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if conflicting.len() > 1 {
+                    return Err(ScafError::ContradictoryRequires {
+                        option,
+                        conflicting,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// where a file goes after the `rename` table, given its path with the option tokens stripped
+    pub fn renamed(&self, path: PathBuf) -> PathBuf {
+        let relative = path
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        if let Some(to) = self.rename.get(&relative) {
+            return PathBuf::from(to);
+        }
+
+        match path.file_name().and_then(|n| self.rename.get(n.to_str()?)) {
+            Some(to) => path.with_file_name(to),
+            None => path,
+        }
+    }
+
+    /// the chosen options along with everything the aliases in them stand for
+    pub fn expand_aliases(&self, chosen: &[String]) -> Result<Vec<String>, ScafError> {
+        let mut expanded = vec![];
+        for option in chosen {
+            follow(option, &|o| self.aliases.get(o), &mut vec![], &mut expanded)
+                .map_err(ScafError::AliasCycle)?;
+        }
+
+        Ok(expanded)
+    }
+
+    /// adds everything the chosen options require, or errors about what's missing when the
+    /// config has `add_required = false`
+    pub fn apply_requires(&self, chosen: &[String]) -> Result<Vec<String>, ScafError> {
+        if self.add_required {
+            return self.requirements(chosen);
+        }
+
+        // only what each option requires directly, since choosing those brings up the rest
+        let mut missing = vec![];
+        for option in chosen {
+            let Some(opt) = self.options.get(option) else {
+                continue;
+            };
+            for required in &opt.requires {
+                if !chosen.contains(required) {
+                    missing.push((option.clone(), required.clone()));
+                }
+            }
+        }
+        if !missing.is_empty() {
+            return Err(ScafError::MissingRequires(missing));
+        }
+
+        Ok(chosen.to_vec())
+    }
+
+    /// the chosen options along with everything they require, all the way down
+    pub fn requirements(&self, chosen: &[String]) -> Result<Vec<String>, ScafError> {
+        let mut expanded = vec![];
+        for option in chosen {
+            let requires = |o: &str| self.options.get(o).map(|opt| &opt.requires);
+            follow(option, &requires, &mut vec![], &mut expanded)
+                .map_err(ScafError::RequiresCycle)?;
+        }
+
+        Ok(expanded)
+    }
+
+    /// whether `name` is something with a value, instead of an option
+    pub fn is_variable(&self, name: &str) -> bool {
+        self.values.contains_key(name)
+            || self.variables.contains_key(name)
+            || BUILTINS.contains(&name)
+    }
+
+    fn raw_globs(&self) -> Result<Vec<Glob>, ScafError> {
+        self.raw_globs
+            .iter()
+            .map(|pattern| {
+                Glob::new(pattern).map_err(|source| ScafError::InvalidGlob {
+                    pattern: pattern.clone(),
+                    source,
+                })
+            })
+            .collect()
+    }
+
+    fn attach_globs(&self) -> Result<Vec<(String, Glob)>, ScafError> {
+        self.attach
+            .iter()
+            .map(|(option, pattern)| {
+                Glob::anchored(pattern)
+                    .map(|g| (option.clone(), g))
+                    .map_err(|source| ScafError::InvalidGlob {
+                        pattern: pattern.clone(),
+                        source,
+                    })
+            })
+            .collect()
+    }
+
+    /// makes sure `value` is one of the variable's `choices` and matches its `pattern`, if it
+    /// has them
+    pub fn check_value(&self, key: &str, value: &str) -> Result<(), ScafError> {
+        let Some(variable) = self.variables.get(key) else {
+            return Ok(());
+        };
+        if !variable.choices.is_empty() && !variable.choices.iter().any(|c| c == value) {
+            return Err(ScafError::InvalidChoice {
+                variable: String::from(key),
+                value: String::from(value),
+                choices: variable.choices.clone(),
+            });
+        }
+        if let Some(message) = variable.pattern_mismatch(value) {
+            return Err(ScafError::PatternMismatch {
+                variable: String::from(key),
+                value: String::from(value),
+                message,
+            });
+        }
+        Ok(())
+    }
+
+    /// `values` along with the given variables, which fall back to their defaults. for when there's
+    /// nobody to prompt
+    pub fn resolve_values(
+        &self,
+        variables: &HashMap<String, String>,
+    ) -> Result<HashMap<String, String>, ScafError> {
+        let mut unknown = variables
+            .keys()
+            .filter(|k| !self.variables.contains_key(*k))
+            .cloned()
+            .collect::<Vec<_>>();
+        if !unknown.is_empty() {
+            unknown.sort();
+            return Err(ScafError::UnknownSetVariables(unknown));
+        }
+
+        let mut given = variables.iter().collect::<Vec<_>>();
+        given.sort();
+        for (key, value) in given {
+            self.check_value(key, value)?;
+        }
+
+        let mut values = self.values.clone();
+        let mut missing = vec![];
+        let mut keys = self.variables.keys().collect::<Vec<_>>();
+        keys.sort();
+        for key in keys {
+            let value = match variables.get(key) {
+                Some(value) => value.clone(),
+                None => match self.variables[key].default_value() {
+                    Some(default) => {
+                        // the other defaults were checked when the config was loaded
+                        self.check_value(key, &default)?;
+                        default
+                    }
+                    None => {
+                        missing.push(key.clone());
+                        continue;
+                    }
+                },
+            };
+            values.insert(key.clone(), value);
+        }
+        if !missing.is_empty() {
+            missing.sort();
+            return Err(ScafError::MissingVariables(missing));
+        }
+
+        Ok(values)
+    }
+
+    /// the options that aren't hidden, in the order the prompt shows them: the ones in `order`
+    /// first, then the rest by name, with options in the same `group` kept together
+    pub fn visible_options(&self) -> Vec<(&String, &Opt)> {
+        let mut options = self
+            .options
+            .iter()
+            .filter(|(_, opt)| !opt.hidden)
+            .collect::<Vec<_>>();
+        options.sort_by_key(|(k, opt)| {
+            let position = self.order.iter().position(|o| o == *k);
+            (position.unwrap_or(usize::MAX), &opt.name)
+        });
+
+        // groups go in the order their first option is in, with the ungrouped options last
+        let mut groups = vec![];
+        for (_, opt) in &options {
+            if let Some(group) = &opt.group {
+                if !groups.contains(&group) {
+                    groups.push(group);
+                }
+            }
+        }
+        options.sort_by_key(|(_, opt)| {
+            opt.group
+                .as_ref()
+                .and_then(|g| groups.iter().position(|group| *group == g))
+                .unwrap_or(usize::MAX)
+        });
+        options
+    }
+
+    /// values for the `BUILTINS`. `scaf.options` is the names of the chosen options that aren't
+    /// hidden, comma separated, and `scaf.options.json` is the same names as a json array
+    pub fn builtin_values(&self, chosen: &[String]) -> HashMap<String, String> {
+        let names = self
+            .visible_options()
+            .into_iter()
+            .filter(|(k, _)| chosen.contains(k))
+            .map(|(_, opt)| opt.name.as_str())
+            .collect::<Vec<_>>();
+
+        HashMap::from([
+            (String::from("scaf.options"), names.join(", ")),
+            (
+                String::from("scaf.options.json"),
+                serde_json::to_string(&names).expect("strings are valid json"),
+            ),
+        ])
+    }
+
+    /// every option key, sorted
+    pub fn get_all_options(&self) -> Vec<String> {
+        let mut options = self.options.keys().cloned().collect::<Vec<_>>();
+        options.sort();
+        options
+    }
+}
@@ -0,0 +1,291 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use console::style;
+use serde::Serialize;
+
+use scaf::{
+    error::ScafError, most_specific, read_files_from_path, source::TemplateSource,
+    undefined_options, variables_in_contents, Config, Conflict, Dependency, File, SizeLimit,
+};
+
+use crate::{Format, LintArgs};
+
+/// path groups that use more options than this aren't checked, since every combination of them
+/// gets tried. variables count for as many options as it takes to try each of their values
+const MAX_COMBINATION_OPTIONS: usize = 16;
+
+/// something wrong with a template
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Problem {
+    UndefinedOption {
+        file: PathBuf,
+        option: String,
+    },
+    UndefinedVariable {
+        file: PathBuf,
+        name: String,
+    },
+    /// choosing `options` and giving the variables `values` leaves the files in `conflict` with no
+    /// way to pick between them
+    Conflict {
+        #[serde(flatten)]
+        conflict: Conflict,
+        options: Vec<String>,
+        #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+        values: BTreeMap<String, String>,
+    },
+    TooManyOptions {
+        path: PathBuf,
+        count: usize,
+    },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::UndefinedOption { file, option } => write!(
+                f,
+                "{}: option `{}` isn't in the config",
+                file.display(),
+                option
+            ),
+            Problem::UndefinedVariable { file, name } => write!(
+                f,
+                "{}: variable `{{{{{}}}}}` isn't in the config",
+                file.display(),
+                name
+            ),
+            Problem::Conflict {
+                conflict,
+                options,
+                values,
+            } => {
+                write!(
+                    f,
+                    "{}: can't choose between files when [{}] are chosen",
+                    conflict.path.display(),
+                    options.join(", ")
+                )?;
+                for (name, value) in values {
+                    write!(f, " and {}={}", name, value)?;
+                }
+                write!(f, ":")?;
+                for file in &conflict.files {
+                    write!(f, "\n  {}", file.source.display())?;
+                }
+                Ok(())
+            }
+            Problem::TooManyOptions { path, count } => write!(
+                f,
+                "{}: uses {} options and variables, too many to check every combination of",
+                path.display(),
+                count
+            ),
+        }
+    }
+}
+
+pub fn run(args: LintArgs) -> Result<(), ScafError> {
+    let template = match &args.template_path {
+        Some(path) => path.clone(),
+        None if Path::new(&args.config).is_file() => PathBuf::from("."),
+        None => return Err(ScafError::ConfigNotFound(Path::new(".").join(&args.config))),
+    };
+    let source = TemplateSource::resolve(&template, args.rev.as_deref(), args.cache.cache())?;
+    let path = source.path.as_path();
+
+    let config = match args.template_root_only {
+        true => Config::from_root(path, &args.config)?,
+        false => Config::from_base(path, &args.config)?,
+    };
+    let (files, _) = read_files_from_path(
+        path,
+        &config,
+        &args.config,
+        SizeLimit::default(),
+        args.include_hidden,
+    )?;
+
+    let mut problems = undefined_options(&config, &files)
+        .into_iter()
+        .map(|(file, option)| Problem::UndefinedOption { file, option })
+        .collect::<Vec<_>>();
+
+    for f in &files {
+        for name in variables_in_contents(f) {
+            if !config.is_variable(name) {
+                problems.push(Problem::UndefinedVariable {
+                    file: f.source.clone(),
+                    name: String::from(name),
+                });
+            }
+        }
+    }
+
+    problems.extend(conflicts(&config, &files));
+
+    if args.format == Format::Json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&problems).expect("problems are valid json")
+        );
+        return match problems.len() {
+            0 => Ok(()),
+            n => Err(ScafError::Lint(n)),
+        };
+    }
+
+    if problems.is_empty() {
+        println!("{}", style("no problems found").green());
+        return Ok(());
+    }
+
+    for problem in &problems {
+        println!("{}", problem);
+    }
+
+    Err(ScafError::Lint(problems.len()))
+}
+
+/// for every output path with more than one file, tries every combination of the options and
+/// variable values those files use (the rest can't change which one wins) and reports the smallest
+/// one where there's a tie
+fn conflicts(config: &Config, files: &[File]) -> Vec<Problem> {
+    let mut by_path: HashMap<&PathBuf, Vec<&File>> = HashMap::new();
+    for f in files {
+        by_path.entry(&f.path).or_default().push(f);
+    }
+
+    let mut groups = by_path
+        .into_iter()
+        .filter(|(_, group)| group.len() > 1)
+        .collect::<Vec<_>>();
+    groups.sort_by_key(|(path, _)| *path);
+
+    let mut problems = vec![];
+    for (path, group) in groups {
+        let options = group
+            .iter()
+            .flat_map(|f| f.depends_on.iter().flat_map(|d| d.options()))
+            .map(String::from)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        let variables = value_candidates(config, &group);
+        let tries = variables
+            .iter()
+            .fold(1usize, |n, (_, values)| n.saturating_mul(values.len()));
+        if options.len() > MAX_COMBINATION_OPTIONS
+            || tries.saturating_mul(1 << options.len()) > 1 << MAX_COMBINATION_OPTIONS
+        {
+            problems.push(Problem::TooManyOptions {
+                path: path.clone(),
+                count: options.len() + variables.len(),
+            });
+            continue;
+        }
+        let assignments = assignments(&variables);
+
+        let mut combinations = (0..1u32 << options.len()).collect::<Vec<_>>();
+        combinations.sort_by_key(|c| c.count_ones());
+
+        'combinations: for combination in combinations {
+            let chosen = options
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| combination & (1 << i) != 0)
+                .map(|(_, o)| o.clone())
+                .collect::<Vec<_>>();
+            let chosen = config
+                .expand_aliases(&chosen)
+                .and_then(|chosen| config.requirements(&chosen))
+                .expect("alias and requires cycles are caught when the config is loaded");
+
+            // combinations that can't actually be chosen don't count
+            if config
+                .exclusive_groups
+                .iter()
+                .any(|g| g.iter().filter(|o| chosen.contains(o)).count() > 1)
+            {
+                continue;
+            }
+
+            for assignment in &assignments {
+                let mut values = config.values.clone();
+                values.extend(assignment.clone());
+                let met = group
+                    .iter()
+                    .copied()
+                    .filter(|f| f.depends_on.iter().all(|d| d.is_met(&chosen, &values)))
+                    .collect::<Vec<_>>();
+                if met.len() < 2 || most_specific(&met).is_some() {
+                    continue;
+                }
+
+                problems.push(Problem::Conflict {
+                    conflict: Conflict::between(path.clone(), &met),
+                    options: chosen,
+                    values: assignment.clone(),
+                });
+                break 'combinations;
+            }
+        }
+    }
+
+    problems
+}
+
+/// the values worth trying for each variable that `{name=value}` tokens in `group` check: its
+/// `choices`, or for variables that can be anything, the values the files check for and `None` for
+/// one that none of them do. `values` from the config can't change, so they aren't tried
+fn value_candidates(config: &Config, group: &[&File]) -> Vec<(String, Vec<Option<String>>)> {
+    let mut checked: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for d in group.iter().flat_map(|f| &f.depends_on) {
+        if let Dependency::Value { name, value } = d {
+            checked.entry(name).or_default().insert(value);
+        }
+    }
+
+    checked
+        .into_iter()
+        .filter_map(|(name, checked)| {
+            let variable = config.variables.get(name)?;
+            let candidates = if variable.choices.is_empty() {
+                checked
+                    .into_iter()
+                    .map(|v| Some(String::from(v)))
+                    .chain([None])
+                    .collect()
+            } else {
+                variable.choices.iter().cloned().map(Some).collect()
+            };
+            Some((String::from(name), candidates))
+        })
+        .collect()
+}
+
+/// every way of giving each variable one of its candidate values, leaving it out for `None`
+fn assignments(variables: &[(String, Vec<Option<String>>)]) -> Vec<BTreeMap<String, String>> {
+    let mut assignments = vec![BTreeMap::new()];
+    for (name, candidates) in variables {
+        assignments = assignments
+            .into_iter()
+            .flat_map(|assignment| {
+                candidates.iter().map(move |candidate| {
+                    let mut assignment = assignment.clone();
+                    if let Some(value) = candidate {
+                        assignment.insert(name.clone(), value.clone());
+                    }
+                    assignment
+                })
+            })
+            .collect();
+    }
+    assignments
+}
@@ -1,243 +1,1717 @@
-use clap::Parser;
-use dialoguer::MultiSelect;
-use regex::Regex;
-use serde::Deserialize;
+mod init;
+mod lint;
+mod picker;
+mod watch;
+
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use console::style;
+use dialoguer::{Confirm, Input, MultiSelect, Select};
+use rayon::prelude::*;
+use scaf::{
+    check_exclusive_groups, check_output_path, dedupe_files,
+    error::ScafError,
+    escapes_output, interpolate_path, match_ignoring_case, merge_templates, read_dirs_from_path,
+    read_files_from_paths, relative_path, render, replace_file_paths,
+    source::{is_template, Cache, TemplateSource},
+    undefined_options, unmet_dependencies, validate_selection, write_file, Config, Dependency,
+    File, LargeFile, MergeStrategy, SizeLimit,
+};
+use serde::{Deserialize, Serialize};
 use std::{
-    cell::OnceCell,
-    collections::{HashMap, HashSet},
-    path::{Path, PathBuf},
-    process,
-    sync::OnceLock,
+    collections::{BTreeMap, HashMap},
+    io::IsTerminal,
+    path::{Component, Path, PathBuf},
+    process::{Command, ExitCode},
+    sync::atomic::{AtomicBool, Ordering},
 };
 
-fn main() {
-    let args = Args::parse();
-    let path = Path::new(&args.template_path);
+/// set while files are being written, so even a second ctrl-c waits for the writes to stop and get
+/// rolled back
+static WRITING: AtomicBool = AtomicBool::new(false);
+/// ctrl-c was pressed. whatever is running stops at the next `check_interrupted` and returns
+/// `Cancelled`, so cloned templates and extracted archives get cleaned up on the way out
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
 
-    if !path.exists() {
-        eprintln!("Path does not exist: {}", path.display());
-        process::exit(1);
+    ctrlc::set_handler(|| {
+        // the second ctrl-c is for when something is stuck, like reading stdin
+        if !INTERRUPTED.swap(true, Ordering::SeqCst) || WRITING.load(Ordering::SeqCst) {
+            return;
+        }
+        // prompts hide the cursor while they're open
+        let _ = console::Term::stderr().show_cursor();
+        eprintln!("{}", style("cancelled").dim().for_stderr());
+        std::process::exit(130);
+    })
+    .expect("ctrl-c handler is only set once");
+
+    let result = match cli.command {
+        Commands::New(args) if args.watch => watch::run(*args),
+        Commands::New(args) => run(*args),
+        Commands::Lint(args) => lint::run(args),
+        Commands::Init(args) => init::run(args),
+        Commands::Completions { shell } => {
+            clap_complete::generate(shell, &mut Cli::command(), "scaf", &mut std::io::stdout());
+            Ok(())
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        // hooks and git get the ctrl-c too, so they fail instead of being cancelled
+        Err(e) if matches!(e, ScafError::Cancelled) || INTERRUPTED.load(Ordering::SeqCst) => {
+            eprintln!("{}", style("cancelled").dim().for_stderr());
+            ExitCode::from(130)
+        }
+        Err(e) => {
+            eprintln!("{} {}", style("Error:").red().bold().for_stderr(), e);
+            ExitCode::FAILURE
+        }
     }
+}
+
+fn run(args: Args) -> Result<(), ScafError> {
+    scaffold(args).map(|_| ())
+}
 
-    let config = Config::from_base(&args.template_path);
+/// errors with `Cancelled` once ctrl-c has been pressed
+fn check_interrupted() -> Result<(), ScafError> {
+    match INTERRUPTED.load(Ordering::SeqCst) {
+        true => Err(ScafError::Cancelled),
+        false => Ok(()),
+    }
+}
 
-    let chosen = select_options(&config);
-    let files = read_files_from_path(path);
+/// everything `scaf new` does, returning what was chosen like it's saved in the lock file, or
+/// `None` if nothing got chosen, like with `--list`
+fn scaffold(mut args: Args) -> Result<Option<Lock>, ScafError> {
+    args.prompt_paths()?;
+    // have to stay alive until the end so cloned templates don't get cleaned up early
+    let sources = args
+        .templates()
+        .iter()
+        .map(|t| TemplateSource::resolve(t, args.rev.as_deref(), args.cache()))
+        .collect::<Result<Vec<_>, _>>()?;
+    check_interrupted()?;
+    let paths = sources.iter().map(|s| s.path.as_path()).collect::<Vec<_>>();
+    // files from every template end up inside of the first one
+    let path = paths[0];
 
-    let files = dedupe_files(files, &chosen);
+    let load = |path: &Path| match args.template_root_only {
+        true => Config::from_root(path, &args.config),
+        false => Config::from_base(path, &args.config),
+    };
+    let mut config = load(path)?;
+    for other in &paths[1..] {
+        config.merge(load(other)?)?;
+    }
+    if args.list {
+        list_options(&config);
+        return Ok(None);
+    }
+    // every path is a template with `--explain`, so there's no output to check
+    if !args.streams() && !args.explain {
+        for template in &paths {
+            check_output_path(template, args.output_path())?;
+        }
+    }
+
+    let limit = SizeLimit {
+        max_bytes: args.max_file_size,
+        skip: args.skip_large,
+    };
+    let (files, skipped) =
+        read_files_from_paths(&paths, &config, &args.config, limit, args.include_hidden)?;
+    for LargeFile { path: file, size } in skipped {
+        if args.verbosity() > Verbosity::Quiet {
+            eprintln!(
+                "{} skipping {}, it's {} bytes",
+                style("Warning:").yellow().bold().for_stderr(),
+                file.display(),
+                size
+            );
+        }
+    }
+    check_undefined_options(&config, &files, &args)?;
+    if args.explain {
+        explain_options(&config, &files, &paths);
+        return Ok(None);
+    }
+
+    // the script is code from the template just like hooks are, so it's turned off the same way
+    if let Some(script) = &config.path_transform {
+        if args.no_hooks || args.dry_run {
+            if args.verbosity() > Verbosity::Quiet {
+                eprintln!(
+                    "{} not running {} with {}, so paths are left as they are",
+                    style("Warning:").yellow().bold().for_stderr(),
+                    script.display(),
+                    if args.no_hooks {
+                        "--no-hooks"
+                    } else {
+                        "--dry-run"
+                    }
+                );
+            }
+            config.path_transform = None;
+        }
+    }
+
+    let replay = match &args.replay {
+        Some(lock_path) => Some(Lock::read(lock_path, &config)?),
+        None => None,
+    };
+    let from_stdin = match args.select_stdin {
+        true => Some(read_selection(std::io::stdin().lock())?),
+        false => None,
+    };
+    let selected = match args
+        .select
+        .as_ref()
+        .or(from_stdin.as_ref())
+        .or(replay.as_ref().map(|l| &l.options))
+    {
+        Some(selected) if args.ignore_case => {
+            validate_selection(&config, &match_ignoring_case(&config, selected)?)?
+        }
+        Some(selected) => validate_selection(&config, selected)?,
+        None => select_options(&config)?,
+    };
+    let chosen = config.expand_aliases(&selected)?;
+    let chosen = config.apply_requires(&chosen)?;
+    check_exclusive_groups(&config, &chosen)?;
+
+    // values from the command line win over the replayed ones
+    let mut set = replay
+        .map(|l| l.variables.into_iter().collect::<Vec<_>>())
+        .unwrap_or_default();
+    set.extend(args.set.iter().cloned());
+    let variables = prompt_variables(&config, &set)?;
+    let mut values = config.values.clone();
+    values.extend(variables.clone());
+    values.extend(config.builtin_values(&chosen));
+    let mut options = selected;
+    options.sort();
+    let lock = Lock {
+        options,
+        variables: variables.into_iter().collect(),
+    };
+
+    if (args.explain_skips && args.prints(Verbosity::Normal)) || args.prints(Verbosity::Verbose) {
+        print_skips(&files, &paths, &chosen, &values);
+    }
 
-    match std::fs::read_dir(&args.output_path) {
+    let total = files.len();
+    let files = if args.no_strip {
+        // every file keeps the name it has in the template, so they don't get deduped against each
+        // other either
+        let mut files = files;
+        for f in &mut files {
+            // files from `[files]` can come from outside of the template, so they keep the path
+            // they're listed with
+            if config.listed_files.iter().any(|l| l.source == f.source) {
+                continue;
+            }
+            let template = paths[f.layer];
+            f.path = path.join(
+                f.source
+                    .strip_prefix(template)
+                    .expect("read from this template"),
+            );
+        }
+        let mut files = dedupe_files(files, &chosen, &values)?;
+        replace_file_paths(
+            &mut files,
+            path,
+            args.output_path(),
+            args.strip_prefix.as_deref(),
+            config.path_transform.as_deref(),
+        )?;
+        files
+    } else {
+        render(
+            files,
+            path,
+            args.output_path(),
+            &chosen,
+            &values,
+            args.strip_prefix.as_deref(),
+            &config,
+        )?
+    };
+    let unmerged = files.len();
+    let files = merge_templates(files, args.merge_strategy)?;
+    let merged = unmerged - files.len();
+    let files = if args.only.is_empty() {
+        files
+    } else {
+        only_files(files, &paths, &args)
+    };
+    if files.is_empty() {
+        let e = ScafError::NothingToScaffold {
+            chosen: chosen.clone(),
+            files: total,
+        };
+        if args.strict {
+            return Err(e);
+        }
+        // an empty output directory would just be confusing, so nothing gets made either
+        if args.verbosity() != Verbosity::Quiet {
+            eprintln!("{} {}", style("Warning:").yellow().bold().for_stderr(), e);
+        }
+        return Ok(Some(lock));
+    }
+    if args.diff.is_some() {
+        print_diff(&files, args.output_path())?;
+        return Ok(Some(lock));
+    }
+    // everything that would stop the write happens before asking, so nobody says yes for nothing
+    let output_exists = match std::fs::read_dir(args.output_path()) {
+        // nothing gets written to disk when the output is streamed
+        _ if args.streams() => true,
         Ok(d) => {
-            if d.into_iter().count() != 0 {
-                eprintln!("Error: output directory is not empty");
-                process::exit(1);
+            // entries like .git are fine to have already, as long as nothing gets written over
+            let blocking = d
+                .filter_map(Result::ok)
+                .filter(|e| {
+                    let name = e.file_name();
+                    !ALLOWED_EXISTING.iter().any(|a| name == *a)
+                        && !args.allow_existing.iter().any(|a| name == a.as_str())
+                })
+                .count();
+            if !args.force && blocking != 0 {
+                return Err(ScafError::OutputNotEmpty(args.output_path().to_path_buf()));
             }
+            true
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => false,
+        Err(e) => return Err(ScafError::io(args.output_path(), e)),
+    };
+    if !args.streams() && args.overwrite_policy() == OverwritePolicy::Abort {
+        let existing = files
+            .iter()
+            .filter(|f| f.path.exists())
+            .map(|f| f.path.clone())
+            .collect::<Vec<_>>();
+        if !existing.is_empty() {
+            return Err(ScafError::FilesExist(existing));
+        }
+    }
+
+    // there's nobody to ask when stdin isn't a terminal, like in scripts and CI
+    if !args.yes && !args.dry_run && std::io::stdin().is_terminal() {
+        confirm_write(&files, args.output_path())?;
+    }
+    check_interrupted()?;
+
+    let mut made_output = false;
+    if !output_exists && !args.dry_run {
+        std::fs::create_dir_all(args.output_path())
+            .map_err(|e| ScafError::io(args.output_path(), e))?;
+        made_output = true;
+    }
+
+    let mut keep_dirs = vec![];
+    if args.keep_empty_dirs {
+        let mut dirs = vec![];
+        for template in &paths {
+            dirs.extend(
+                read_dirs_from_path(
+                    template,
+                    &config,
+                    &args.config,
+                    &chosen,
+                    &values,
+                    args.include_hidden,
+                )?
+                .into_iter()
+                .map(|dir| relative_path(template, &dir)),
+            );
         }
-        Err(e) => match e.kind() {
-            std::io::ErrorKind::NotFound => {
-                std::fs::create_dir_all(&args.output_path).expect("valid path");
+        keep_dirs = dirs
+            .into_iter()
+            .filter_map(|relative| {
+                let relative =
+                    PathBuf::from(interpolate_path(&relative, &values, config.path_tokens));
+                // directories outside of the prefix can't have anything in them
+                match &args.strip_prefix {
+                    Some(prefix) => relative.strip_prefix(prefix).ok().map(Path::to_path_buf),
+                    None => Some(relative),
+                }
+            })
+            .filter(|relative| !relative.as_os_str().is_empty())
+            .filter(|relative| {
+                args.only.is_empty() || args.only.iter().any(|o| relative.starts_with(o))
+            })
+            .map(|relative| args.output_path().join(relative))
+            // directories with files in them get made anyway
+            .filter(|dir| !files.iter().any(|f| f.path.starts_with(dir)))
+            .collect();
+        keep_dirs.sort();
+        keep_dirs.dedup();
+
+        let mut escaping = vec![];
+        for dir in &keep_dirs {
+            if escapes_output(args.output_path(), dir)? {
+                escaping.push(dir.clone());
             }
-            _ => {
-                eprintln!("Error: {}", e);
-                process::exit(1);
+        }
+        if !escaping.is_empty() {
+            return Err(ScafError::OutsideOutput {
+                output: args.output_path().to_path_buf(),
+                paths: escaping,
+            });
+        }
+    }
+
+    let bytes = files
+        .iter()
+        .map(|f| (f.path.clone(), f.contents.len()))
+        .collect::<HashMap<_, _>>();
+
+    let json = args.format == Format::Json;
+    let hooks = !args.no_hooks && !args.rebuilding && !args.dry_run && !args.streams();
+    if hooks {
+        run_hooks(&config.pre_hooks, args.output_path(), &chosen, json)?;
+    }
+    if args.streams() {
+        write_archive(&files, &keep_dirs, args.output_path())?;
+        return Ok(Some(lock));
+    }
+    let left_out = total - merged - files.len();
+    let to_write = files.len();
+    let written = match write_files(files, &args) {
+        Ok(written) => written,
+        Err(e) => {
+            // the rest was already rolled back, and this only works if it's empty again
+            if made_output && !args.no_rollback {
+                let _ = std::fs::remove_dir(args.output_path());
             }
+            return Err(e);
+        }
+    };
+    let mut manifest = Manifest {
+        options: chosen.clone(),
+        stats: Stats {
+            written: written.len(),
+            left_out,
+            merged,
+            skipped: to_write - written.len(),
+            bytes: written.iter().map(|path| bytes[path]).sum(),
         },
+        files: written
+            .iter()
+            .map(|path| ManifestFile {
+                path: path.clone(),
+                bytes: bytes[path],
+            })
+            .collect(),
+    };
+    manifest.options.sort();
+    manifest.files.sort_by(|a, b| a.path.cmp(&b.path));
+    if !args.dry_run {
+        lock.write(&args.output_path().join(LOCK_FILE))?;
+    }
+    for dir in keep_dirs {
+        if args.dry_run {
+            if args.prints(Verbosity::Normal) {
+                println!("would create {}", style(dir.display()).bold());
+            }
+        } else {
+            std::fs::create_dir_all(&dir).map_err(|e| ScafError::io(&dir, e))?;
+        }
+    }
+    if hooks {
+        run_hooks(&config.post_hooks, args.output_path(), &chosen, json)?;
     }
 
-    let mut files = files.into_iter().collect::<Vec<_>>();
+    if args.prints(Verbosity::Normal) {
+        // a dry run already listed every file
+        if !args.dry_run {
+            print_tree(&written, args.output_path());
+        }
+        let verb = if args.dry_run {
+            "Would scaffold"
+        } else {
+            "Scaffolded"
+        };
+        let stats = &manifest.stats;
+        let merged = match stats.merged {
+            0 => String::new(),
+            n => format!("{} merged, ", n),
+        };
+        println!(
+            "{} {} files into {} {}",
+            style(verb).green().bold(),
+            stats.written,
+            args.output_path().display(),
+            style(format!(
+                "({} bytes, {} left out, {}{} skipped since they exist)",
+                stats.bytes, stats.left_out, merged, stats.skipped
+            ))
+            .dim()
+        );
+    }
 
-    replace_file_paths(&mut files, &args);
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&manifest).expect("manifest is valid json")
+        );
+    }
 
-    for f in &files {
-        println!("path: {}, options: {:?}", f.path.display(), f.depends_on);
+    Ok(Some(lock))
+}
+
+/// a directory in `print_tree`, key: file or directory name
+#[derive(Default)]
+struct Tree(BTreeMap<String, Tree>);
+
+/// prints the written files like the `tree` command does
+fn print_tree(paths: &[PathBuf], output_path: &Path) {
+    let mut root = Tree::default();
+    for path in paths {
+        let relative = path.strip_prefix(output_path).unwrap_or(path);
+        let mut node = &mut root;
+        for component in relative.components() {
+            let name = component.as_os_str().to_string_lossy().into_owned();
+            node = node.0.entry(name).or_default();
+        }
+    }
+
+    fn print_children(tree: &Tree, prefix: &str) {
+        for (i, (name, child)) in tree.0.iter().enumerate() {
+            let last = i == tree.0.len() - 1;
+            let (branch, indent) = if last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+            if child.0.is_empty() {
+                println!("{}{}{}", style(prefix).dim(), style(branch).dim(), name);
+            } else {
+                println!(
+                    "{}{}{}",
+                    style(prefix).dim(),
+                    style(branch).dim(),
+                    style(format!("{}/", name)).bold()
+                );
+                print_children(child, &format!("{}{}", prefix, indent));
+            }
+        }
     }
 
-    write_files(files);
+    println!("{}", style(output_path.display()).bold());
+    print_children(&root, "");
 }
 
-fn write_files(files: Vec<File>) {
-    for f in files {
-        std::fs::write(f.path, f.contents).expect("valid path");
+/// what got scaffolded, printed with `--format json`
+#[derive(Serialize, Debug)]
+struct Manifest {
+    options: Vec<String>,
+    stats: Stats,
+    files: Vec<ManifestFile>,
+}
+
+/// counts of what happened to the template's files
+#[derive(Serialize, Debug)]
+struct Stats {
+    written: usize,
+    /// files whose options weren't chosen, that lost to a more specific file for the same path, or
+    /// that aren't in `--only`
+    left_out: usize,
+    /// files from more than one template that were combined into another one with
+    /// `--merge-strategy`
+    merged: usize,
+    /// files that already existed and weren't written over
+    skipped: usize,
+    /// the size of every written file added up
+    bytes: usize,
+}
+
+#[derive(Serialize, Debug)]
+struct ManifestFile {
+    path: PathBuf,
+    bytes: usize,
+}
+
+/// where the choices from the last run are saved in the output directory
+const LOCK_FILE: &str = ".scaf-lock.toml";
+
+/// the options and variables picked in a run, which `--replay` can use instead of prompting
+#[derive(Serialize, Deserialize, Debug)]
+struct Lock {
+    /// what was chosen, before aliases are expanded
+    options: Vec<String>,
+    #[serde(default)]
+    variables: BTreeMap<String, String>,
+}
+
+impl Lock {
+    /// reads the lock file, making sure everything in it is still in the template's config
+    fn read(path: &Path, config: &Config) -> Result<Self, ScafError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| ScafError::io(path, e))?;
+        let lock = toml::from_str::<Lock>(&contents).map_err(|source| ScafError::ConfigParse {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        let mut unknown = lock
+            .options
+            .iter()
+            .filter(|o| !config.options.contains_key(*o))
+            .chain(
+                lock.variables
+                    .keys()
+                    .filter(|v| !config.variables.contains_key(*v)),
+            )
+            .cloned()
+            .collect::<Vec<_>>();
+        if !unknown.is_empty() {
+            unknown.sort();
+            return Err(ScafError::StaleLock {
+                path: path.to_path_buf(),
+                unknown,
+            });
+        }
+
+        Ok(lock)
+    }
+
+    fn write(&self, path: &Path) -> Result<(), ScafError> {
+        let contents = toml::to_string(self).expect("lock is valid toml");
+        std::fs::write(path, contents).map_err(|e| ScafError::io(path, e))
     }
 }
 
-fn replace_file_paths(files: &mut Vec<File>, args: &Args) {
-    for f in files {
-        let stripped = f
-            .path
-            .strip_prefix(args.template_path.clone())
-            .expect("prefix is the same");
+/// runs each command in a shell from the output directory, stopping at the first one that fails.
+/// `to_stderr` sends the hook's output to stderr so it doesn't mix with json on stdout
+fn run_hooks(
+    hooks: &[String],
+    dir: &Path,
+    chosen: &[String],
+    to_stderr: bool,
+) -> Result<(), ScafError> {
+    for hook in hooks {
+        check_interrupted()?;
+        let mut command = if cfg!(windows) {
+            let mut c = Command::new("cmd");
+            c.arg("/C");
+            c
+        } else {
+            let mut c = Command::new("sh");
+            c.arg("-c");
+            c
+        };
+
+        if to_stderr {
+            command.stdout(std::io::stderr());
+        }
+
+        let status = command
+            .arg(hook)
+            .current_dir(dir)
+            .env("SCAF_OPTIONS", chosen.join(","))
+            .status()
+            .map_err(|e| ScafError::Hook {
+                command: hook.clone(),
+                message: e.to_string(),
+            })?;
 
-        f.path = args.output_path.join(stripped);
+        if !status.success() {
+            return Err(ScafError::Hook {
+                command: hook.clone(),
+                message: status.to_string(),
+            });
+        }
     }
+
+    Ok(())
 }
 
-fn read_files_from_path(path: &Path) -> Vec<File> {
-    let mut f_vec = vec![];
-    let files = std::fs::read_dir(path).unwrap();
-    for file in files.into_iter().filter_map(|f| match f {
-        Ok(f) => Some(f),
-        Err(e) => {
-            eprintln!("Error reading a file: {}", e);
-            None
+/// prints a unified diff of every file against the one in `existing`, along with the files that
+/// would be new and the ones that are only in `existing`
+fn print_diff(files: &[File], existing: &Path) -> Result<(), ScafError> {
+    let mut files = files.iter().collect::<Vec<_>>();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    for f in &files {
+        let relative = f.path.strip_prefix(existing).unwrap_or(&f.path);
+        let old = match std::fs::read(&f.path) {
+            Ok(old) => Some(old),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(ScafError::io(&f.path, e)),
+        };
+        if old.as_deref() == Some(f.contents.as_slice()) {
+            continue;
         }
-    }) {
-        // pain
-        let path = String::from(file.path().as_os_str().to_str().expect("normal string"));
 
-        if path.contains("scaf.toml") {
+        let (Ok(old_text), Ok(new_text)) = (
+            std::str::from_utf8(old.as_deref().unwrap_or_default()),
+            std::str::from_utf8(&f.contents),
+        ) else {
+            match old {
+                None => println!(
+                    "{}",
+                    style(format!("new binary file {}", relative.display())).green()
+                ),
+                Some(_) => println!("binary file {} differs", style(relative.display()).bold()),
+            }
             continue;
+        };
+
+        if old.is_none() {
+            println!(
+                "{}",
+                style(format!("new file {}", relative.display())).green()
+            );
+        }
+        let header = relative.display().to_string();
+        let diff = similar::TextDiff::from_lines(old_text, new_text);
+        for line in diff
+            .unified_diff()
+            .header(&format!("a/{}", header), &format!("b/{}", header))
+            .to_string()
+            .lines()
+        {
+            match line.chars().next() {
+                Some('+') => println!("{}", style(line).green()),
+                Some('-') => println!("{}", style(line).red()),
+                Some('@') => println!("{}", style(line).cyan()),
+                _ => println!("{}", line),
+            }
+        }
+    }
+
+    // everything in the existing project that the template doesn't make anymore
+    let mut dirs = vec![existing.to_path_buf()];
+    let mut removed = vec![];
+    while let Some(dir) = dirs.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(ScafError::io(&dir, e)),
+        };
+        for entry in entries {
+            let path = entry.map_err(|e| ScafError::io(&dir, e))?.path();
+            let name = path.file_name().unwrap_or_default();
+            if dir == existing && ALLOWED_EXISTING.iter().any(|a| name == *a) {
+                continue;
+            }
+            if path.is_dir() {
+                dirs.push(path);
+            } else if !files.iter().any(|f| f.path == path) {
+                removed.push(path);
+            }
         }
+    }
+    removed.sort();
+    for path in removed {
+        let relative = path.strip_prefix(existing).unwrap_or(&path);
+        println!(
+            "{}",
+            style(format!(
+                "only in {}: {}",
+                existing.display(),
+                relative.display()
+            ))
+            .red()
+        );
+    }
+
+    Ok(())
+}
+
+/// writes the files and directories to stdout as a tar archive instead of to disk, with paths
+/// relative to the output directory
+fn write_archive(files: &[File], dirs: &[PathBuf], output_path: &Path) -> Result<(), ScafError> {
+    let stdout = std::io::stdout().lock();
+    let mut archive = tar::Builder::new(stdout);
+    let error = |e| ScafError::io(output_path, e);
+
+    for dir in dirs {
+        let relative = dir.strip_prefix(output_path).unwrap_or(dir);
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Directory);
+        header.set_mode(0o755);
+        header.set_size(0);
+        archive
+            .append_data(&mut header, relative, std::io::empty())
+            .map_err(error)?;
+    }
+
+    // sorted so the same template always makes the same archive
+    let mut files = files.iter().collect::<Vec<_>>();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    for f in files {
+        let relative = f.path.strip_prefix(output_path).unwrap_or(&f.path);
+        let mut header = tar::Header::new_gnu();
+        header.set_mode(f.mode.unwrap_or(0o644));
+        header.set_size(f.contents.len() as u64);
+        archive
+            .append_data(&mut header, relative, f.contents.as_slice())
+            .map_err(error)?;
+    }
 
-        let options = options_in_file(&path);
+    archive.finish().map_err(error)
+}
+
+/// entries that can already be in the output directory without needing `--force`
+const ALLOWED_EXISTING: &[&str] = &[".git", LOCK_FILE];
+
+/// prints how many files are about to be written to each top level directory and asks before
+/// going ahead. it goes to stderr along with the prompt so json on stdout stays clean
+fn confirm_write(files: &[File], output_path: &Path) -> Result<(), ScafError> {
+    let mut by_dir: BTreeMap<String, usize> = BTreeMap::new();
+    for f in files {
+        let relative = f.path.strip_prefix(output_path).unwrap_or(&f.path);
+        let mut components = relative.components();
+        let first = components.next();
+        let dir = match (first, components.next()) {
+            (Some(dir), Some(_)) => format!("{}/", dir.as_os_str().to_string_lossy()),
+            _ => String::from("./"),
+        };
+        *by_dir.entry(dir).or_default() += 1;
+    }
+
+    eprintln!(
+        "{} files will be written to {}:",
+        files.len(),
+        style(output_path.display()).bold().for_stderr()
+    );
+    let width = by_dir.keys().map(String::len).max().unwrap_or(0);
+    for (dir, count) in &by_dir {
+        eprintln!("  {:<width$}  {}", dir, style(count).dim().for_stderr());
+    }
 
-        f_vec.push(create_file(path, options));
+    let confirmed = Confirm::new()
+        .with_prompt("Continue?")
+        .default(true)
+        .interact_opt()
+        .map_err(ScafError::prompt)?
+        .ok_or(ScafError::Cancelled)?;
+    if !confirmed {
+        return Err(ScafError::Aborted);
     }
-    f_vec
+
+    Ok(())
 }
 
-// TODO: not OnceLock ??
-static RE: OnceLock<Regex> = OnceLock::new();
+/// writes the files, returning the paths that were actually written (or would be, in a dry run)
+fn write_files(files: Vec<File>, args: &Args) -> Result<Vec<PathBuf>, ScafError> {
+    let policy = args.overwrite_policy();
 
-fn options_in_file(path: &String) -> Vec<String> {
-    let re = RE.get_or_init(|| Regex::new(r"\{.+\}").expect("valid regex"));
+    // prompts and printing have to happen one file at a time, so figure out what's getting
+    // written first and only do the writes in parallel
+    let mut to_write = vec![];
+    for f in files {
+        if f.path.exists() && !should_overwrite(&f.path, policy, args)? {
+            if args.prints(Verbosity::Verbose) || (args.dry_run && args.prints(Verbosity::Normal)) {
+                println!(
+                    "skipping {}{}",
+                    f.path.display(),
+                    style(", it already exists").dim()
+                );
+            }
+            continue;
+        }
 
-    let caps = match re.captures(&path) {
-        None => vec![],
-        Some(c) => c
+        let options = f
+            .depends_on
             .iter()
-            .filter_map(|i| i.map(|i| i.as_str().trim_matches(|c| c == '{' || c == '}')))
-            .map(String::from)
-            .collect(),
+            .map(Dependency::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        if args.dry_run && args.prints(Verbosity::Normal) {
+            println!(
+                "would write {} {}",
+                style(f.path.display()).bold(),
+                style(format!(
+                    "({} bytes), options: [{}]",
+                    f.contents.len(),
+                    options
+                ))
+                .dim()
+            );
+        } else if args.prints(Verbosity::Verbose) {
+            println!(
+                "path: {}, {}",
+                style(f.path.display()).bold(),
+                style(format!("options: [{}]", options)).dim()
+            );
+        }
+
+        to_write.push(f);
+    }
+
+    let written = to_write.iter().map(|f| f.path.clone()).collect();
+    if args.dry_run {
+        return Ok(written);
+    }
+
+    let mut pool = rayon::ThreadPoolBuilder::new();
+    if let Some(jobs) = args.jobs {
+        pool = pool.num_threads(jobs.into());
+    }
+    let pool = pool.build().expect("thread pool can be built");
+
+    let rollback = if args.no_rollback {
+        None
+    } else {
+        Some(Rollback::new(&to_write, args.output_path())?)
     };
+    WRITING.store(true, Ordering::SeqCst);
+    let results = pool.install(|| {
+        to_write
+            .par_iter()
+            // the files that already got written are rolled back below
+            .filter(|_| !INTERRUPTED.load(Ordering::SeqCst))
+            .map(|f| (&f.path, write_file(f)))
+            .collect::<Vec<_>>()
+    });
+    WRITING.store(false, Ordering::SeqCst);
+    let mut errors = vec![];
+    for (path, result) in results {
+        match result {
+            Ok(true) => {}
+            Ok(false) => {
+                if args.verbosity() != Verbosity::Quiet {
+                    eprintln!(
+                        "{} couldn't rename a temp file into place, so {} was written directly",
+                        style("Warning:").yellow().bold().for_stderr(),
+                        path.display()
+                    );
+                }
+            }
+            Err(e) => errors.push(e),
+        }
+    }
+    let interrupted = INTERRUPTED.load(Ordering::SeqCst);
+    if !errors.is_empty() || interrupted {
+        if let Some(rollback) = rollback {
+            rollback.undo();
+        }
+        if interrupted {
+            return Err(ScafError::Cancelled);
+        }
+        return Err(ScafError::Multiple(errors));
+    }
 
-    let mut options = HashSet::new();
+    Ok(written)
+}
 
-    for c in caps {
-        for o in c.split(',') {
-            options.insert(String::from(o));
+/// what the output directory looked like before any files were written, so it can be put back
+/// if some of them fail
+struct Rollback {
+    /// files that get written over, along with what was in them
+    existing: Vec<(PathBuf, Vec<u8>, std::fs::Permissions)>,
+    /// files that weren't there yet
+    created: Vec<PathBuf>,
+    /// directories that weren't there yet, deepest first
+    dirs: Vec<PathBuf>,
+}
+
+impl Rollback {
+    fn new(files: &[File], output_path: &Path) -> Result<Self, ScafError> {
+        let mut rollback = Rollback {
+            existing: vec![],
+            created: vec![],
+            dirs: vec![],
+        };
+
+        for f in files {
+            if f.path.is_file() {
+                let contents = std::fs::read(&f.path).map_err(|e| ScafError::io(&f.path, e))?;
+                let permissions = std::fs::metadata(&f.path)
+                    .map_err(|e| ScafError::io(&f.path, e))?
+                    .permissions();
+                rollback
+                    .existing
+                    .push((f.path.clone(), contents, permissions));
+            } else if !f.path.exists() {
+                rollback.created.push(f.path.clone());
+            }
+
+            let missing = f
+                .path
+                .ancestors()
+                .skip(1)
+                .take_while(|dir| dir.starts_with(output_path) && !dir.exists())
+                .map(Path::to_path_buf);
+            rollback.dirs.extend(missing);
+        }
+        rollback
+            .dirs
+            .sort_by_key(|dir| std::cmp::Reverse(dir.components().count()));
+        rollback.dirs.dedup();
+
+        Ok(rollback)
+    }
+
+    /// puts everything back. it's only done after something already failed, so anything that
+    /// can't be undone is left alone instead of piling on more errors
+    fn undo(self) {
+        for path in self.created {
+            let _ = std::fs::remove_file(path);
+        }
+        for (path, contents, permissions) in self.existing {
+            let _ = std::fs::write(&path, contents);
+            let _ = std::fs::set_permissions(&path, permissions);
+        }
+        // remove_dir only removes empty directories, so nothing that was already there is lost
+        for dir in self.dirs {
+            let _ = std::fs::remove_dir(dir);
         }
     }
+}
 
-    options.into_iter().collect()
+/// decides what to do with a file that's already in the output directory
+fn should_overwrite(path: &Path, policy: OverwritePolicy, args: &Args) -> Result<bool, ScafError> {
+    match policy {
+        OverwritePolicy::Overwrite => Ok(true),
+        OverwritePolicy::Skip => Ok(false),
+        // existing files were already checked for before anything got written
+        OverwritePolicy::Abort => Ok(true),
+        // don't ask about writes that won't happen
+        OverwritePolicy::Prompt if args.dry_run => Ok(true),
+        OverwritePolicy::Prompt => {
+            let relative = path.strip_prefix(args.output_path()).unwrap_or(path);
+            let choice = Select::new()
+                .with_prompt(format!("{} already exists", relative.display()))
+                .items(&["overwrite", "skip", "abort"])
+                .default(1)
+                .interact_opt()
+                .map_err(ScafError::prompt)?
+                .ok_or(ScafError::Cancelled)?;
+
+            match choice {
+                0 => Ok(true),
+                1 => Ok(false),
+                _ => Err(ScafError::Aborted),
+            }
+        }
+    }
 }
 
-fn create_file(path: String, options: Vec<String>) -> File {
-    let re = RE.get_or_init(|| Regex::new(r"\{.+\}").expect("valid regex"));
-    let contents = std::fs::read_to_string(&path).expect("valid utf8");
+/// templates with more options than this get a search box in the picker
+const FILTER_AFTER: usize = 12;
+/// the heading for options without a `group`, when some of them have one
+const UNGROUPED: &str = "Other";
+
+/// how wide option descriptions are wrapped to when stderr isn't a terminal
+const FALLBACK_WIDTH: usize = 80;
 
-    let path = re.replace_all(&path, "");
-    let path = PathBuf::from(path.into_owned());
+/// how far in both prompts put an option's name, after the cursor and checkbox
+const ITEM_INDENT: usize = 6;
 
-    File {
-        path,
-        contents,
-        depends_on: options,
+/// option keys for `--select-stdin`, one per line. blank lines and lines starting with `#` are
+/// left out
+fn read_selection(reader: impl std::io::BufRead) -> Result<Vec<String>, ScafError> {
+    let mut keys = vec![];
+    for line in reader.lines() {
+        let line = line.map_err(|e| ScafError::io("stdin", e))?;
+        let key = line.trim();
+        if !key.is_empty() && !key.starts_with('#') {
+            keys.push(String::from(key));
+        }
     }
+    Ok(keys)
 }
 
-fn select_options(config: &Config) -> Vec<String> {
-    let mut map = config.options.iter().collect::<Vec<_>>();
-    map.sort_by(|(_, a), (_, b)| a.cmp(b));
-    let items = map.iter().map(|(_, v)| v).collect::<Vec<_>>();
+fn select_options(config: &Config) -> Result<Vec<String>, ScafError> {
+    let map = config.visible_options();
+    let width = console::Term::stderr()
+        .size_checked()
+        .map_or(FALLBACK_WIDTH, |(_, columns)| columns as usize)
+        .saturating_sub(ITEM_INDENT);
+    let items = map
+        .iter()
+        .map(|(_, v)| v.label(&config.values, width, ITEM_INDENT))
+        .collect::<Vec<_>>();
+    // has to be built after sorting so it lines up with the items
+    let defaults = map
+        .iter()
+        .map(|(k, _)| config.defaults.contains(k))
+        .collect::<Vec<_>>();
+    let groups = if map.iter().any(|(_, v)| v.group.is_some()) {
+        map.iter()
+            .map(|(_, v)| v.group.clone().unwrap_or_else(|| String::from(UNGROUPED)))
+            .collect()
+    } else {
+        vec![]
+    };
 
-    // TODO: instructions
-    let chosen = MultiSelect::new().items(&items).interact().unwrap();
+    if let Some(title) = &config.meta.title {
+        eprintln!("{}", style(title).bold().for_stderr());
+    }
+    if let Some(description) = &config.meta.description {
+        eprintln!("{}", style(description).dim().for_stderr());
+    }
+
+    // dialoguer can't show headings, so groups always get the picker
+    let chosen = if items.len() > FILTER_AFTER || !groups.is_empty() {
+        picker::filtered_multi_select(
+            "Choose options (type to filter, space to toggle, enter to confirm, esc to cancel)",
+            &map.iter().map(|(_, v)| v.name.as_str()).collect::<Vec<_>>(),
+            &items,
+            &defaults,
+            &groups,
+        )
+    } else {
+        MultiSelect::new()
+            .with_prompt("Choose options (space to toggle, enter to confirm, esc to cancel)")
+            .report(false)
+            .items(&items)
+            .defaults(&defaults)
+            .interact_opt()
+    }
+    .map_err(ScafError::prompt)?
+    .ok_or(ScafError::Cancelled)?;
+    // just the names, since descriptions can be several lines long
+    eprintln!(
+        "{} {}",
+        style("Options:").bold().for_stderr(),
+        chosen
+            .iter()
+            .map(|&i| map[i].1.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
     let chosen = chosen.iter().map(|&i| map[i].0.clone()).collect::<Vec<_>>();
 
-    println!("{:?}", chosen);
+    Ok(chosen)
+}
+
+/// the name of the template, for an output directory in the current one. `url#subdir` is named
+/// after the subdirectory, and extensions like `.git` and `.tar.gz` are left off
+fn default_output(template: &Path) -> String {
+    let template = template.to_string_lossy();
+    let template = match template.split_once('#') {
+        Some((_, subdir)) => subdir,
+        None => &template,
+    };
+    let name = template
+        .trim_end_matches('/')
+        .rsplit(['/', ':'])
+        .next()
+        .unwrap_or_default();
+    let name = [".git", ".tar.gz", ".tgz", ".tar", ".zip"]
+        .iter()
+        .find_map(|ext| name.strip_suffix(ext))
+        .unwrap_or(name);
+
+    match name {
+        // anywhere inside of the current directory would be inside of the template
+        "." => String::from("../out"),
+        "" | ".." => String::from("out"),
+        name => String::from(name),
+    }
+}
+
+/// prints what the template is from `[meta]`, then every option in the config, sorted by key
+fn list_options(config: &Config) {
+    if let Some(title) = &config.meta.title {
+        println!("{}", style(title).bold());
+    }
+    if let Some(description) = &config.meta.description {
+        println!("{}", style(description).dim());
+    }
+    if config.meta.title.is_some() || config.meta.description.is_some() {
+        println!();
+    }
+
+    let mut options = config.options.iter().collect::<Vec<_>>();
+    options.sort_by_key(|(k, _)| *k);
+    let width = options.iter().map(|(k, _)| k.len()).max().unwrap_or(0);
 
-    chosen
+    for (key, opt) in options {
+        match &opt.description {
+            Some(description) => println!("{:width$}  {} - {}", key, opt.name, description),
+            None => println!("{:width$}  {}", key, opt.name),
+        }
+    }
 }
 
-fn dedupe_files(files: Vec<File>, chosen: &Vec<String>) -> HashSet<File> {
-    let files: Vec<_> = files
+/// keeps the files inside of one of the `--only` paths, warning about the paths that don't have any
+/// files in them
+fn only_files(files: Vec<File>, templates: &[&Path], args: &Args) -> Vec<File> {
+    let mut used = vec![false; args.only.len()];
+    let files = files
         .into_iter()
-        // first filter out all the ones that don't depend on any of the chosen options
-        .filter(|f| f.depends_on.iter().all(|o| chosen.contains(o)))
+        .filter(|f| {
+            let source = f
+                .source
+                .strip_prefix(templates[f.layer])
+                .unwrap_or(&f.source);
+            let output = f.path.strip_prefix(args.output_path()).unwrap_or(&f.path);
+            let mut keep = false;
+            for (only, used) in args.only.iter().zip(&mut used) {
+                if source.starts_with(only) || output.starts_with(only) {
+                    *used = true;
+                    keep = true;
+                }
+            }
+            keep
+        })
         .collect();
 
-    let mut deduped_files = HashSet::new();
-    // now we check for duplicates in the paths and try to resolve them
-    for f in &files {
-        // definitely a better way of doing this i just dont know it
-        let indexes = files
+    if args.verbosity() != Verbosity::Quiet {
+        for (only, _) in args.only.iter().zip(used).filter(|(_, used)| !used) {
+            eprintln!(
+                "{} --only {} doesn't have any files in it for the chosen options",
+                style("Warning:").yellow().bold().for_stderr(),
+                only.display()
+            );
+        }
+    }
+
+    files
+}
+
+/// prints every option in the config, sorted by key, with the template files that choosing it can
+/// add. files that need an option to not be chosen aren't listed under it
+fn explain_options(config: &Config, files: &[File], templates: &[&Path]) {
+    let mut options = config.options.iter().collect::<Vec<_>>();
+    options.sort_by_key(|(k, _)| *k);
+
+    for (key, opt) in options {
+        println!("{} {}", style(key).bold(), style(&opt.name).dim());
+        let mut paths = files
             .iter()
-            .enumerate()
-            .filter_map(|(i, f2)| if f.path == f2.path { Some(i) } else { None })
+            .filter(|f| {
+                f.depends_on.iter().any(|d| match d {
+                    Dependency::Option(o) => o == key,
+                    Dependency::AnyOf(options) => options.contains(key),
+                    Dependency::Not(_) | Dependency::Value { .. } => false,
+                })
+            })
+            .map(|f| relative_path(templates[f.layer], &f.source))
             .collect::<Vec<_>>();
+        paths.sort();
+        paths.dedup();
 
-        if indexes.len() > 1 {
-            let dups = indexes
-                .into_iter()
-                .map(|i| files[i].clone())
-                .collect::<Vec<_>>();
-
-            let max = dups.iter().map(|a| a.depends_on.len()).max().unwrap();
-            let maxes = dups
-                .iter()
-                .filter(|i| i.depends_on.len() == max)
-                .collect::<Vec<_>>();
-            if maxes.len() > 1 {
-                eprintln!(
-                    "Error: can't choose between files with similar options. filename: {}",
-                    maxes[0].path.display()
-                );
-                process::exit(1);
-            } else {
-                deduped_files.insert(maxes[0].clone());
-            }
-        } else {
-            deduped_files.insert(f.clone());
+        if paths.is_empty() {
+            println!("  {}", style("no files").dim());
+        }
+        for path in paths {
+            println!("  {}", path);
+        }
+    }
+}
+
+/// prints the files that are left out, under each dependency that isn't met, so it's easy to see
+/// what choosing an option would add
+fn print_skips(
+    files: &[File],
+    templates: &[&Path],
+    chosen: &[String],
+    values: &HashMap<String, String>,
+) {
+    let mut skips: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for f in files {
+        for dependency in unmet_dependencies(f, chosen, values) {
+            skips
+                .entry(dependency.to_string())
+                .or_default()
+                .push(relative_path(templates[f.layer], &f.source));
         }
     }
+    if skips.is_empty() {
+        return;
+    }
 
-    deduped_files
+    println!("files left out for the chosen options:");
+    for (dependency, mut paths) in skips {
+        paths.sort();
+        paths.dedup();
+        println!("  {}", style(dependency).bold());
+        for path in paths {
+            println!("    {}", path);
+        }
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct File {
-    path: PathBuf,
-    contents: String,
-    depends_on: Vec<String>,
+/// finds options used in file names that aren't in the config, which are usually typos. these
+/// files could never be scaffolded, so it's a warning or an error with `--strict`
+fn check_undefined_options(config: &Config, files: &[File], args: &Args) -> Result<(), ScafError> {
+    let undefined = undefined_options(config, files);
+    if undefined.is_empty() {
+        return Ok(());
+    }
+
+    let e = ScafError::UndefinedOptions(undefined);
+    if args.strict {
+        return Err(e);
+    }
+    if args.verbosity() != Verbosity::Quiet {
+        eprintln!("{} {}", style("Warning:").yellow().bold().for_stderr(), e);
+    }
+
+    Ok(())
+}
+
+/// asks for the value of every variable in the config, unless it was given with `--set`
+fn prompt_variables(
+    config: &Config,
+    set: &[(String, String)],
+) -> Result<HashMap<String, String>, ScafError> {
+    let unknown = set
+        .iter()
+        .filter(|(k, _)| !config.variables.contains_key(k))
+        .map(|(k, _)| k.clone())
+        .collect::<Vec<_>>();
+    if !unknown.is_empty() {
+        return Err(ScafError::UnknownSetVariables(unknown));
+    }
+
+    let mut variables = config.variables.iter().collect::<Vec<_>>();
+    variables.sort_by_key(|(k, _)| *k);
+
+    let mut values = HashMap::new();
+    for (key, variable) in variables {
+        let value = match set.iter().rev().find(|(k, _)| k == key) {
+            Some((_, v)) => {
+                config.check_value(key, v)?;
+                v.clone()
+            }
+            None if !variable.choices.is_empty() => {
+                let default = variable
+                    .default_value()
+                    .and_then(|d| variable.choices.iter().position(|c| *c == d))
+                    .unwrap_or(0);
+                let choice = Select::new()
+                    .with_prompt(&variable.prompt)
+                    .items(&variable.choices)
+                    .default(default)
+                    .interact_opt()
+                    .map_err(ScafError::prompt)?
+                    .ok_or(ScafError::Cancelled)?;
+                variable.choices[choice].clone()
+            }
+            None => {
+                let mut input = Input::<String>::new();
+                input.with_prompt(&variable.prompt);
+                if variable.pattern.is_some() {
+                    // asks again until the value matches
+                    input.validate_with(|value: &String| match variable.pattern_mismatch(value) {
+                        Some(message) => Err(message),
+                        None => Ok(()),
+                    });
+                }
+                match variable.default_value() {
+                    // a git default that isn't set can be left empty
+                    Some(default) if default.is_empty() => {
+                        input.allow_empty(true);
+                    }
+                    Some(default) => {
+                        input.default(default);
+                    }
+                    None => {}
+                }
+                input.interact_text().map_err(ScafError::prompt)?
+            }
+        };
+        values.insert(key.clone(), value);
+    }
+
+    Ok(values)
 }
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
-struct Args {
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// scaffold a project from a template
+    New(Box<Args>),
+    /// check a template for mistakes, across every combination of options
+    Lint(LintArgs),
+    /// start a new template with an example config and file
+    Init(InitArgs),
+    /// print a completion script for a shell, e.g. `scaf completions zsh > _scaf`
+    Completions { shell: clap_complete::Shell },
+}
+
+/// how git templates that were cloned before get reused
+#[derive(clap::Args, Debug, Clone)]
+struct CacheArgs {
+    /// clone git templates from scratch instead of reusing an earlier clone
+    #[arg(long, conflicts_with = "refresh")]
+    no_cache: bool,
+    /// fetch cached git templates even when `--rev` is a commit that can't have changed
+    #[arg(long)]
+    refresh: bool,
+}
+
+impl CacheArgs {
+    fn cache(&self) -> Cache {
+        if self.no_cache {
+            Cache::Off
+        } else if self.refresh {
+            Cache::Refresh
+        } else {
+            Cache::Use
+        }
+    }
+}
+
+#[derive(clap::Args, Debug)]
+struct InitArgs {
+    /// where to make the template
     #[arg()]
-    template_path: PathBuf,
+    dir: PathBuf,
+    /// make the template even if the directory isn't empty, writing over any of the files it makes
+    #[arg(long)]
+    force: bool,
+}
+
+#[derive(clap::Args, Debug)]
+struct LintArgs {
+    /// a template directory, or a git url (`url#subdir` to use a directory inside the repo).
+    /// defaults to the current directory if it has a config
     #[arg()]
-    output_path: PathBuf,
+    template_path: Option<PathBuf>,
+    /// the config file to use, relative to the template
+    #[arg(long, default_value = "scaf.toml")]
+    config: String,
+    /// the branch, tag, or commit to check out when the template is a git url
+    #[arg(long)]
+    rev: Option<String>,
+    #[command(flatten)]
+    cache: CacheArgs,
+    /// check dotfiles in the template too, for templates meant to be scaffolded with `--include-hidden`
+    #[arg(long)]
+    include_hidden: bool,
+    /// only check the template's top level config, for templates meant to be scaffolded with
+    /// `--template-root-only`
+    #[arg(long)]
+    template_root_only: bool,
+    /// how to report problems. json is an array of every problem found, for editors and ci
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
 }
 
-#[derive(Deserialize, Debug)]
-struct Config {
-    /// the options that scaf should give the user, key: variable name, value: human readable name
-    options: HashMap<String, String>,
+#[derive(clap::Args, Debug, Clone)]
+struct Args {
+    /// template directories or git urls (`url#subdir` to use a directory inside the repo), then
+    /// where to scaffold to, or `-` to write a tar archive to stdout. later templates go on top of
+    /// earlier ones. with `--list` or `--diff` these are all templates. the ones that are left out
+    /// get asked for, and inside of a template the current directory is the template
+    #[arg(value_name = "PATH")]
+    paths: Vec<PathBuf>,
+    /// print the options the template has and exit
+    #[arg(long)]
+    list: bool,
+    /// print the files that each option adds and exit
+    #[arg(long, conflicts_with = "list")]
+    explain: bool,
+    /// print how scaffolding would change an existing project instead of writing anything
+    #[arg(long, value_name = "EXISTING_DIR")]
+    diff: Option<PathBuf>,
+    /// the config file to use, relative to the template
+    #[arg(long, default_value = "scaf.toml")]
+    config: String,
+    /// the branch, tag, or commit to check out when the template is a git url
+    #[arg(long)]
+    rev: Option<String>,
+    #[command(flatten)]
+    cache: CacheArgs,
+    /// option keys to use instead of prompting, e.g. `--select typescript,eslint`
+    #[arg(long, value_delimiter = ',')]
+    select: Option<Vec<String>>,
+    /// read option keys from stdin instead of prompting, one per line. blank lines and lines
+    /// starting with `#` are left out
+    #[arg(long, conflicts_with_all = ["select", "replay", "watch"])]
+    select_stdin: bool,
+    /// match the keys from `--select` and `--select-stdin` to options without caring about case,
+    /// so `TypeScript` chooses `typescript`
+    #[arg(long)]
+    ignore_case: bool,
+    /// use the options and variables saved in a `.scaf-lock.toml` by an earlier run instead of
+    /// prompting
+    #[arg(long, conflicts_with = "select")]
+    replay: Option<PathBuf>,
+    /// value for a variable instead of prompting for it, e.g. `--set project_name=myapp`
+    #[arg(long, value_parser = parse_key_value)]
+    set: Vec<(String, String)>,
+    /// only scaffold the files inside of this path, which can be relative to the template or to the
+    /// output, e.g. `--only ci`. can be passed more than once
+    #[arg(long, value_name = "PATH", value_parser = parse_relative)]
+    only: Vec<PathBuf>,
+    /// print the files that would be written without touching the disk
+    #[arg(long)]
+    dry_run: bool,
+    /// a directory inside the template to scaffold from, so `--strip-prefix template` writes
+    /// `template/src/main.rs` to `src/main.rs`. every file has to be inside of it
+    #[arg(long)]
+    strip_prefix: Option<PathBuf>,
+    /// the biggest a template file can be, in bytes or with a suffix like `10MB` or `512K`
+    #[arg(long, default_value = "10MB", value_parser = parse_size)]
+    max_file_size: u64,
+    /// leave out template files bigger than `--max-file-size` instead of erroring
+    #[arg(long)]
+    skip_large: bool,
+    /// scaffold dotfiles in the template too. `.gitignore`, `.github`, and a few others always are
+    #[arg(long)]
+    include_hidden: bool,
+    /// only use the template's top level config. configs in subdirectories are ignored instead of
+    /// merged in, and still aren't scaffolded
+    #[arg(long)]
+    template_root_only: bool,
+    /// what to do when more than one template has a file at the same path
+    #[arg(long, value_enum, default_value_t = MergeStrategy::Error)]
+    merge_strategy: MergeStrategy,
+    /// keep the `{option}` tokens in file names and leave `{{var}}`s and `scaf:if` blocks alone, to
+    /// see which files a template uses for the chosen options. for debugging templates, not for
+    /// real projects
+    #[arg(long)]
+    no_strip: bool,
+    /// write the files without asking first
+    #[arg(long, short = 'y')]
+    yes: bool,
+    /// print every file that gets written, and the ones that get left out like `--explain-skips`
+    #[arg(short, long, conflicts_with = "quiet")]
+    verbose: bool,
+    /// print the files that get left out, under each option that would need to be chosen (or not
+    /// chosen) for them, before writing anything
+    #[arg(long)]
+    explain_skips: bool,
+    /// only print errors
+    #[arg(short, long)]
+    quiet: bool,
+    /// how many files to write at once, defaults to the number of cpus
+    #[arg(long, short = 'j', value_parser = clap::value_parser!(u16).range(1..))]
+    jobs: Option<u16>,
+    /// how to report what was scaffolded
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    /// don't run the hooks in scaf.toml
+    #[arg(long)]
+    no_hooks: bool,
+    /// set on `--watch` rebuilds, which leave out the hooks but otherwise scaffold like the first
+    /// build did, `path_transform` included
+    #[arg(skip)]
+    rebuilding: bool,
+    /// error instead of warning about mistakes in the template
+    #[arg(long)]
+    strict: bool,
+    /// create template directories that end up without any files in them, which are left out by
+    /// default
+    #[arg(long)]
+    keep_empty_dirs: bool,
+    /// an entry that can already be in the output directory without needing `--force`, on top of
+    /// `.git`
+    #[arg(long)]
+    allow_existing: Vec<String>,
+    /// scaffold into the output directory even if it isn't empty
+    #[arg(long)]
+    force: bool,
+    /// replace files that already exist in the output directory, same as `--overwrite-policy overwrite`
+    #[arg(long, conflicts_with = "overwrite_policy")]
+    overwrite: bool,
+    /// leave the files that were written when some others fail, instead of putting the output
+    /// directory back the way it was
+    #[arg(long)]
+    no_rollback: bool,
+    /// what to do with files that already exist in the output directory
+    #[arg(long, value_enum, default_value_t = OverwritePolicy::Abort)]
+    overwrite_policy: OverwritePolicy,
+    /// scaffold again whenever a template changes, with the same options and variables. rebuilds
+    /// write over the output and skip the hooks
+    #[arg(long, conflicts_with_all = ["list", "diff", "dry_run"])]
+    watch: bool,
 }
 
-impl Config {
-    fn from_base(base_path: &PathBuf) -> Self {
-        let config_file_path = base_path.join("scaf.toml");
-        let config_file = match std::fs::read(config_file_path.clone()) {
-            Ok(file) => String::from_utf8(file).expect("valid utf8"),
-            Err(e) => {
-                eprintln!("Error reading {:?}: {}", config_file_path, e);
-                process::exit(1);
+impl Args {
+    fn cache(&self) -> Cache {
+        self.cache.cache()
+    }
+
+    /// the templates to merge, which is every path but the output
+    fn templates(&self) -> &[PathBuf] {
+        if self.list || self.explain || self.diff.is_some() {
+            &self.paths
+        } else {
+            &self.paths[..self.paths.len() - 1]
+        }
+    }
+
+    /// the last path, or the existing project for `--diff` since it stands in for the output
+    /// directory
+    fn output_path(&self) -> &Path {
+        self.diff
+            .as_deref()
+            .or(self.paths.last().map(PathBuf::as_path))
+            .expect("clap requires a path")
+    }
+
+    /// asks for the template if there wasn't one, and then for the output path if it's needed.
+    /// with `--yes` the output path is a directory named after the template without asking
+    fn prompt_paths(&mut self) -> Result<(), ScafError> {
+        // inside of a template, the only path given is where to scaffold it to, unless that's a
+        // template too
+        let in_template = Path::new(&self.config).is_file();
+        let templates_only = self.list || self.explain || self.diff.is_some();
+        match self.paths.as_slice() {
+            [] if in_template => self.paths.push(PathBuf::from(".")),
+            [path] if in_template && !templates_only && !is_template(path, &self.config) => {
+                self.paths.insert(0, PathBuf::from("."))
             }
+            _ => {}
+        }
+
+        if self.paths.is_empty() {
+            let template = Input::<String>::new()
+                .with_prompt("Template")
+                .interact_text()
+                .map_err(ScafError::prompt)?;
+            self.paths.push(PathBuf::from(template));
+        }
+        if templates_only || self.paths.len() > 1 {
+            return Ok(());
+        }
+
+        let default = default_output(&self.paths[0]);
+        let output = if self.yes {
+            default
+        } else {
+            Input::<String>::new()
+                .with_prompt("Output directory")
+                .default(default)
+                .interact_text()
+                .map_err(ScafError::prompt)?
         };
+        self.paths.push(PathBuf::from(output));
 
-        match toml::from_str::<Config>(&config_file) {
-            Ok(config) => config,
-            Err(e) => {
-                eprintln!("Error parsing scaf.toml: {}", e);
-                process::exit(1);
-            }
+        Ok(())
+    }
+
+    /// `--watch` needs templates on disk to watch, and somewhere to write to
+    fn check_watch(&self) {
+        let message = if self.streams() {
+            "--watch can't write to stdout"
+        } else if self.templates().iter().any(|t| !t.exists()) {
+            "--watch only works with templates that are on disk, not git urls"
+        } else {
+            return;
+        };
+        let mut cli = Cli::command();
+        cli.build();
+        cli.find_subcommand_mut("new")
+            .expect("new is a subcommand")
+            .error(clap::error::ErrorKind::ArgumentConflict, message)
+            .exit();
+    }
+
+    fn verbosity(&self) -> Verbosity {
+        if self.quiet {
+            Verbosity::Quiet
+        } else if self.verbose {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
+        }
+    }
+
+    /// whether something at this verbosity should be printed to stdout, which is kept for the
+    /// manifest in json mode and the archive when streaming
+    fn prints(&self, verbosity: Verbosity) -> bool {
+        self.format == Format::Text && !self.streams() && self.verbosity() >= verbosity
+    }
+
+    /// whether the output path is `-`, so a tar archive goes to stdout instead of files to disk
+    fn streams(&self) -> bool {
+        !self.dry_run && self.diff.is_none() && self.output_path() == Path::new("-")
+    }
+
+    fn overwrite_policy(&self) -> OverwritePolicy {
+        if self.overwrite {
+            OverwritePolicy::Overwrite
+        } else {
+            self.overwrite_policy
         }
     }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Verbosity {
+    Quiet,
+    Normal,
+    Verbose,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// for people
+    Text,
+    /// a json object with the chosen options and written files, and nothing else on stdout
+    Json,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum OverwritePolicy {
+    /// ask about each file
+    Prompt,
+    /// leave the existing file alone
+    Skip,
+    /// replace the existing file
+    Overwrite,
+    /// stop before writing anything
+    Abort,
+}
+
+/// a number of bytes, optionally with a `K`, `M`, or `G` suffix (with or without a `B`)
+fn parse_size(s: &str) -> Result<u64, String> {
+    let upper = s.trim().to_uppercase();
+    let digits = upper.trim_end_matches('B');
+    let (number, multiplier) = match digits.chars().last() {
+        Some('K') => (&digits[..digits.len() - 1], 1024),
+        Some('M') => (&digits[..digits.len() - 1], 1024 * 1024),
+        Some('G') => (&digits[..digits.len() - 1], 1024 * 1024 * 1024),
+        _ => (digits, 1),
+    };
+
+    let number = number
+        .trim()
+        .parse::<u64>()
+        .map_err(|_| format!("expected a size like `10MB`, got `{}`", s))?;
+    number
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("`{}` is too large of a size", s))
+}
+
+/// a path inside of the template or output, without any `./` so it can be compared with
+/// `starts_with`
+fn parse_relative(s: &str) -> Result<PathBuf, String> {
+    let path = Path::new(s);
+    if path.is_absolute() || path.components().any(|c| c == Component::ParentDir) {
+        return Err(format!(
+            "expected a path inside of the template, got `{}`",
+            s
+        ));
+    }
+
+    let relative = path
+        .components()
+        .filter(|c| *c != Component::CurDir)
+        .collect::<PathBuf>();
+    if relative.as_os_str().is_empty() {
+        return Err(String::from("expected a path inside of the template"));
+    }
+
+    Ok(relative)
+}
 
-    fn get_all_options(&self) -> Vec<String> {
-        self.options.keys().cloned().collect::<Vec<_>>()
+fn parse_key_value(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((k, v)) => Ok((String::from(k), String::from(v))),
+        None => Err(format!("expected key=value, got `{}`", s)),
     }
 }
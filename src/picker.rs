@@ -0,0 +1,138 @@
+use std::io;
+
+use console::{style, Key, Term};
+
+/// most rows shown at once, the rest are scrolled to
+const MAX_ROWS: usize = 15;
+
+/// a line in the list, either a heading or the item at that position in the matching ones
+enum Row<'a> {
+    Heading(&'a str),
+    Item(usize),
+}
+
+/// a multi select with a search box on top, for templates with too many options to scroll through.
+/// typing narrows the list down to the items whose name has the query in it, space toggles, enter
+/// confirms, and esc cancels. `names` are plain text to match against, while `items` are what gets
+/// shown and can be styled and several lines long. `groups` is the heading each item goes under, or
+/// empty for no headings, and items in the same group have to be next to each other. returns the
+/// indices of the chosen items, or `None` if it was cancelled
+pub fn filtered_multi_select(
+    prompt: &str,
+    names: &[&str],
+    items: &[String],
+    defaults: &[bool],
+    groups: &[String],
+) -> io::Result<Option<Vec<usize>>> {
+    let term = Term::stderr();
+    let mut checked = defaults.to_vec();
+    checked.resize(items.len(), false);
+    let mut query = String::new();
+    let mut cursor = 0;
+    let mut drawn = 0;
+
+    term.hide_cursor()?;
+    let confirmed = loop {
+        let needle = query.to_lowercase();
+        let matching = (0..items.len())
+            .filter(|&i| names[i].to_lowercase().contains(&needle))
+            .collect::<Vec<_>>();
+        cursor = cursor.min(matching.len().saturating_sub(1));
+
+        let mut rows = vec![];
+        for (position, &i) in matching.iter().enumerate() {
+            if !groups.is_empty() && (position == 0 || groups[matching[position - 1]] != groups[i])
+            {
+                rows.push(Row::Heading(&groups[i]));
+            }
+            rows.push(Row::Item(position));
+        }
+        let cursor_row = rows
+            .iter()
+            .position(|r| matches!(r, Row::Item(p) if *p == cursor))
+            .unwrap_or(0);
+
+        let height = MAX_ROWS.min((term.size().0 as usize).saturating_sub(2).max(1));
+        // keeps the cursor on screen
+        let first = cursor_row.saturating_sub(height - 1);
+
+        term.clear_last_lines(drawn)?;
+        let mut lines = vec![format!(
+            "{} {}",
+            style(format!("{}:", prompt)).bold(),
+            query
+        )];
+        let mut shown = 0;
+        for row in rows.iter().skip(first).take(height) {
+            let position = match row {
+                Row::Heading(heading) => {
+                    lines.push(format!("  {}", style(heading).bold()));
+                    continue;
+                }
+                Row::Item(position) => *position,
+            };
+            let i = matching[position];
+            let check = if checked[i] { "[x]" } else { "[ ]" };
+            let line = format!("{} {}", check, items[i]);
+            if position == cursor {
+                lines.push(format!("{} {}", style(">").cyan(), style(line).cyan()));
+            } else {
+                lines.push(format!("  {}", line));
+            }
+            shown += 1;
+        }
+        if matching.is_empty() {
+            lines.push(style("  nothing matches").dim().to_string());
+        } else if rows.len() > height {
+            lines.push(
+                style(format!("  {} of {} shown", shown, matching.len()))
+                    .dim()
+                    .to_string(),
+            );
+        }
+        for line in &lines {
+            term.write_line(line)?;
+        }
+        // items with long descriptions take up more than one line
+        drawn = lines.iter().map(|l| l.lines().count().max(1)).sum();
+
+        let key = match term.read_key() {
+            Ok(key) => key,
+            Err(e) => {
+                let _ = term.show_cursor();
+                return Err(e);
+            }
+        };
+        match key {
+            Key::Enter => break true,
+            Key::Escape => break false,
+            Key::ArrowUp if !matching.is_empty() => {
+                cursor = cursor.checked_sub(1).unwrap_or(matching.len() - 1);
+            }
+            Key::ArrowDown if !matching.is_empty() => cursor = (cursor + 1) % matching.len(),
+            Key::Char(' ') => {
+                if let Some(&i) = matching.get(cursor) {
+                    checked[i] = !checked[i];
+                }
+            }
+            Key::Char(c) if !c.is_control() => {
+                query.push(c);
+                cursor = 0;
+            }
+            Key::Backspace => {
+                query.pop();
+                cursor = 0;
+            }
+            _ => {}
+        }
+    };
+    term.clear_last_lines(drawn)?;
+    term.show_cursor()?;
+
+    if !confirmed {
+        return Ok(None);
+    }
+    Ok(Some(
+        (0..items.len()).filter(|&i| checked[i]).collect::<Vec<_>>(),
+    ))
+}
@@ -0,0 +1,282 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use tempfile::TempDir;
+
+use crate::error::ScafError;
+
+/// what to do with the clones of git templates that are kept between runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Cache {
+    /// reuse clones of pinned commits as is, and fetch everything else first
+    #[default]
+    Use,
+    /// fetch even if the commit is pinned
+    Refresh,
+    /// clone into a temp directory like there was no cache
+    Off,
+}
+
+/// a template directory on disk. remote templates and archives are checked out into a temp
+/// directory that gets deleted when this is dropped
+pub struct TemplateSource {
+    pub path: PathBuf,
+    _temp: Option<TempDir>,
+}
+
+impl TemplateSource {
+    /// local paths are used as is, `.tar`, `.tar.gz`, and `.zip` files are extracted, and git urls
+    /// get cloned first, or reused from the cache. `url#subdir` uses a directory
+    /// inside of the repo as the template
+    pub fn resolve(template: &Path, rev: Option<&str>, cache: Cache) -> Result<Self, ScafError> {
+        let template_str = template.to_str().unwrap_or_default();
+
+        if let Some(kind) = ArchiveKind::from_path(template_str) {
+            let temp = TempDir::new().map_err(|e| ScafError::io(std::env::temp_dir(), e))?;
+            extract(template, kind, temp.path())?;
+
+            return Ok(TemplateSource {
+                path: temp.path().to_path_buf(),
+                _temp: Some(temp),
+            });
+        }
+
+        if !is_git_url(template_str) {
+            if !template.exists() {
+                return Err(ScafError::TemplateNotFound(template.to_path_buf()));
+            }
+
+            return Ok(TemplateSource {
+                path: template.to_path_buf(),
+                _temp: None,
+            });
+        }
+
+        let (url, subdir) = match template_str.split_once('#') {
+            Some((url, subdir)) => (url, Some(subdir)),
+            None => (template_str, None),
+        };
+
+        let (root, temp) = match cache_dir(url, rev).filter(|_| cache != Cache::Off) {
+            Some(dir) => {
+                update_cached(url, rev, &dir, cache)?;
+                (dir, None)
+            }
+            None => {
+                let temp = TempDir::new().map_err(|e| ScafError::io(std::env::temp_dir(), e))?;
+                clone(url, rev, temp.path())?;
+                (temp.path().to_path_buf(), Some(temp))
+            }
+        };
+
+        let path = match subdir {
+            Some(subdir) => root.join(subdir),
+            None => root,
+        };
+        if !path.exists() {
+            return Err(ScafError::TemplateNotFound(template.to_path_buf()));
+        }
+
+        Ok(TemplateSource { path, _temp: temp })
+    }
+}
+
+/// whether `path` looks like something `resolve` can use as a template: a git url, an archive, or
+/// a directory with a config in it
+pub fn is_template(path: &Path, config_name: &str) -> bool {
+    let s = path.to_str().unwrap_or_default();
+    is_git_url(s) || ArchiveKind::from_path(s).is_some() || path.join(config_name).is_file()
+}
+
+/// where the clone of `url` at `rev` is kept, or `None` if there's no cache directory on this
+/// platform. the name is the end of the url so it's easy to find, plus a hash of the whole thing
+/// so different urls never share a clone
+fn cache_dir(url: &str, rev: Option<&str>) -> Option<PathBuf> {
+    let dirs = directories::ProjectDirs::from("", "", "scaf")?;
+    let readable = url
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .rsplit(['/', ':'])
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '.' || *c == '-' || *c == '_')
+        .take(32)
+        .collect::<String>();
+    let key = format!(
+        "{}-{:016x}",
+        readable,
+        fnv1a(&[url.as_bytes(), b"@", rev.unwrap_or("HEAD").as_bytes()])
+    );
+
+    Some(dirs.cache_dir().join("git").join(key))
+}
+
+/// 64 bit fnv-1a. `DefaultHasher` isn't promised to stay the same between rust versions, and the
+/// cache has to outlive those
+fn fnv1a(parts: &[&[u8]]) -> u64 {
+    parts
+        .iter()
+        .flat_map(|part| part.iter())
+        .fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+        })
+}
+
+/// the url a cached clone was made from, so a clone of something else is never reused
+fn origin_url(dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["config", "--get", "remote.origin.url"])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// a full commit hash can't move, so a clone of one never needs fetching
+fn is_pinned(rev: Option<&str>) -> bool {
+    rev.is_some_and(|rev| rev.len() == 40 && rev.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// makes sure `dir` has an up to date clone of `url` at `rev`. a cached clone that's broken, or
+/// that was cloned from somewhere else, gets thrown away and cloned again
+fn update_cached(url: &str, rev: Option<&str>, dir: &Path, cache: Cache) -> Result<(), ScafError> {
+    if dir.join(".git").is_dir() && origin_url(dir).as_deref() == Some(url) {
+        if is_pinned(rev) && cache != Cache::Refresh {
+            return Ok(());
+        }
+        if fetch(url, rev, dir).is_ok() {
+            return Ok(());
+        }
+    }
+
+    if dir.exists() {
+        std::fs::remove_dir_all(dir).map_err(|e| ScafError::io(dir, e))?;
+    }
+    if let Some(parent) = dir.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| ScafError::io(parent, e))?;
+    }
+    let result = clone(url, rev, dir);
+    // a half finished clone would look like a cached one next time
+    if result.is_err() {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+    result
+}
+
+/// updates a cached clone to the newest commit of `rev`, or the default branch without one
+fn fetch(url: &str, rev: Option<&str>, dir: &Path) -> Result<(), ScafError> {
+    let mut fetch = Command::new("git");
+    fetch.arg("-C").arg(dir).args(["fetch", "--quiet"]);
+    if rev.is_none() {
+        fetch.args(["--depth", "1"]);
+    }
+    fetch.arg(url).arg(rev.unwrap_or("HEAD"));
+    run_git(url, &mut fetch)?;
+
+    let mut checkout = Command::new("git");
+    checkout
+        .arg("-C")
+        .arg(dir)
+        .args(["checkout", "--quiet", "--force", "FETCH_HEAD"]);
+    run_git(url, &mut checkout)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ArchiveKind {
+    Tar,
+    TarGz,
+    Zip,
+}
+
+impl ArchiveKind {
+    fn from_path(s: &str) -> Option<Self> {
+        if s.ends_with(".tar.gz") || s.ends_with(".tgz") {
+            Some(ArchiveKind::TarGz)
+        } else if s.ends_with(".tar") {
+            Some(ArchiveKind::Tar)
+        } else if s.ends_with(".zip") {
+            Some(ArchiveKind::Zip)
+        } else {
+            None
+        }
+    }
+}
+
+/// unpacks the archive into `into`. both the tar and zip crates refuse to write entries outside of
+/// the directory they're extracting to
+fn extract(archive: &Path, kind: ArchiveKind, into: &Path) -> Result<(), ScafError> {
+    let file = std::fs::File::open(archive).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            ScafError::TemplateNotFound(archive.to_path_buf())
+        } else {
+            ScafError::io(archive, e)
+        }
+    })?;
+    let archive_error = |message: String| ScafError::Archive {
+        path: archive.to_path_buf(),
+        message,
+    };
+
+    match kind {
+        ArchiveKind::Tar => tar::Archive::new(file)
+            .unpack(into)
+            .map_err(|e| archive_error(e.to_string())),
+        ArchiveKind::TarGz => tar::Archive::new(flate2::read::GzDecoder::new(file))
+            .unpack(into)
+            .map_err(|e| archive_error(e.to_string())),
+        ArchiveKind::Zip => zip::ZipArchive::new(file)
+            .and_then(|mut zip| zip.extract(into))
+            .map_err(|e| archive_error(e.to_string())),
+    }
+}
+
+fn is_git_url(s: &str) -> bool {
+    ["https://", "http://", "ssh://", "git://", "file://", "git@"]
+        .iter()
+        .any(|prefix| s.starts_with(prefix))
+}
+
+fn clone(url: &str, rev: Option<&str>, into: &Path) -> Result<(), ScafError> {
+    let mut clone = Command::new("git");
+    clone.args(["clone", "--quiet"]);
+    // a specific rev might not be the tip of anything, so it needs the full history
+    if rev.is_none() {
+        clone.args(["--depth", "1"]);
+    }
+    clone.arg(url).arg(into);
+    run_git(url, &mut clone)?;
+
+    if let Some(rev) = rev {
+        let mut checkout = Command::new("git");
+        checkout
+            .arg("-C")
+            .arg(into)
+            .args(["checkout", "--quiet", rev]);
+        run_git(url, &mut checkout)?;
+    }
+
+    Ok(())
+}
+
+fn run_git(url: &str, command: &mut Command) -> Result<(), ScafError> {
+    let output = command.output().map_err(|e| ScafError::Git {
+        url: String::from(url),
+        message: format!("couldn't run git: {}", e),
+    })?;
+
+    if !output.status.success() {
+        return Err(ScafError::Git {
+            url: String::from(url),
+            message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use std::{
+    collections::BTreeSet,
+    io,
+    path::Path,
+    sync::mpsc,
+    time::{Duration, Instant},
+};
+
+use console::style;
+use notify::{RecursiveMode, Watcher};
+
+use scaf::error::ScafError;
+
+use crate::Args;
+
+/// how long the template has to stay the same before rebuilding, since saving in an editor or
+/// checking out a branch changes lots of files at once
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// scaffolds once like `scaf new`, then again every time a template changes. every build writes
+/// over the last one, and rebuilds use what the first run chose so they never prompt, and don't
+/// run hooks
+pub fn run(mut args: Args) -> Result<(), ScafError> {
+    args.prompt_paths()?;
+    args.check_watch();
+    // the output is usually from the last time this was run
+    args.overwrite = true;
+    args.force = true;
+
+    let (tx, rx) = mpsc::channel();
+    let watch_error = |path: &Path, e: notify::Error| ScafError::io(path, io::Error::other(e));
+    let mut watcher =
+        notify::recommended_watcher(tx).map_err(|e| watch_error(&args.templates()[0], e))?;
+    for template in args.templates() {
+        watcher
+            .watch(template, RecursiveMode::Recursive)
+            .map_err(|e| watch_error(template, e))?;
+    }
+
+    let Some(lock) = crate::scaffold(args.clone())? else {
+        return Ok(());
+    };
+
+    // the choices come straight from the first run instead of its lock file, since runs that
+    // don't scaffold anything don't write one
+    let mut rebuild = args.clone();
+    rebuild.select = Some(lock.options);
+    rebuild.select_stdin = false;
+    rebuild.replay = None;
+    rebuild.set = lock.variables.into_iter().collect();
+    rebuild.yes = true;
+    rebuild.rebuilding = true;
+    rebuild.quiet = true;
+    rebuild.verbose = false;
+
+    if !args.quiet {
+        eprintln!(
+            "{}",
+            style("watching for changes, ctrl-c to stop")
+                .dim()
+                .for_stderr()
+        );
+    }
+
+    loop {
+        // checking every so often, since ctrl-c doesn't wake this up on its own
+        let event = match rx.recv_timeout(DEBOUNCE) {
+            Ok(event) => event,
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                crate::check_interrupted()?;
+                continue;
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(()),
+        };
+        let mut changed = BTreeSet::new();
+        let mut event = Some(event);
+        let mut quiet_since = Instant::now();
+        loop {
+            if let Some(Ok(e)) = event.take() {
+                // just reading a file isn't a change
+                if !e.kind.is_access() {
+                    changed.extend(e.paths);
+                    quiet_since = Instant::now();
+                }
+            }
+            let left = DEBOUNCE.saturating_sub(quiet_since.elapsed());
+            if left.is_zero() {
+                break;
+            }
+            match rx.recv_timeout(left) {
+                Ok(e) => event = Some(e),
+                Err(mpsc::RecvTimeoutError::Timeout) => break,
+                Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(()),
+            }
+        }
+        if changed.is_empty() {
+            continue;
+        }
+
+        match crate::run(rebuild.clone()) {
+            Ok(()) => {
+                if !args.quiet {
+                    eprintln!(
+                        "{} {} after {} changed files",
+                        style("Rebuilt").green().bold().for_stderr(),
+                        args.output_path().display(),
+                        changed.len()
+                    );
+                }
+            }
+            Err(ScafError::Cancelled) => return Err(ScafError::Cancelled),
+            // the template is probably half edited, so this waits for the next change
+            Err(e) => {
+                crate::check_interrupted()?;
+                eprintln!("{} {}", style("Error:").red().bold().for_stderr(), e);
+            }
+        }
+    }
+}
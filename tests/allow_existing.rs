@@ -0,0 +1,59 @@
+mod common;
+
+use std::{path::Path, process::Command};
+
+use tempfile::TempDir;
+
+const CONFIG: &str = "[options]\na = \"A\"\n";
+const FILES: &[(&str, &str)] = &[("src/main.rs", "fn main() {}\n")];
+
+fn scaf_new(template: &Path, extra: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(template)
+        .arg(template.with_file_name("out"))
+        .args(["--select", "a", "--yes"])
+        .args(extra)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn allowed_entries_dont_need_force() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let out = dir.path().join("out");
+    std::fs::create_dir_all(out.join(".git")).unwrap();
+    std::fs::create_dir_all(out.join(".idea")).unwrap();
+    std::fs::write(out.join("notes.txt"), "mine\n").unwrap();
+
+    let output = scaf_new(
+        &template,
+        &["--allow-existing", ".idea", "--allow-existing", "notes.txt"],
+    );
+    assert!(output.status.success(), "{:?}", output);
+    assert!(out.join("src/main.rs").exists());
+    assert_eq!(
+        std::fs::read_to_string(out.join("notes.txt")).unwrap(),
+        "mine\n"
+    );
+}
+
+#[test]
+fn other_entries_still_need_force() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let out = dir.path().join("out");
+    std::fs::create_dir_all(out.join(".idea")).unwrap();
+    std::fs::write(out.join("notes.txt"), "mine\n").unwrap();
+
+    let output = scaf_new(&template, &["--allow-existing", ".idea"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("output directory is not empty"),
+        "{}",
+        stderr
+    );
+    assert!(!out.join("src").exists());
+}
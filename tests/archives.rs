@@ -0,0 +1,95 @@
+mod common;
+
+use std::{io::Write, path::Path, process::Command};
+
+use tempfile::TempDir;
+
+const CONFIG: &str = "[options]\nts = \"TypeScript\"\n";
+const FILES: &[(&str, &str)] = &[("src/index{ts}.ts", "ts\n"), ("src/index{!ts}.js", "js\n")];
+
+fn tar(template: &Path, into: impl Write) {
+    let mut builder = tar::Builder::new(into);
+    builder.append_dir_all(".", template).unwrap();
+    builder.into_inner().unwrap().flush().unwrap();
+}
+
+/// zips the same files `common::template` writes, since the zip crate can't add a directory
+fn zip(into: std::fs::File) {
+    let mut zip = zip::ZipWriter::new(into);
+    for (name, contents) in [("scaf.toml", CONFIG)].iter().chain(FILES) {
+        zip.start_file(*name, zip::write::FileOptions::default())
+            .unwrap();
+        zip.write_all(contents.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap();
+}
+
+fn scaffold_from(archive: &Path, out: &Path) {
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(archive)
+        .arg(out)
+        .args(["--select", "ts", "--yes"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}: {:?}",
+        archive.display(),
+        output
+    );
+    assert_eq!(
+        std::fs::read_to_string(out.join("src/index.ts")).unwrap(),
+        "ts\n"
+    );
+    assert!(!out.join("src/index.js").exists());
+}
+
+#[test]
+fn tars_are_extracted() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let archive = dir.path().join("template.tar");
+    tar(&template, std::fs::File::create(&archive).unwrap());
+
+    scaffold_from(&archive, &dir.path().join("out"));
+}
+
+#[test]
+fn gzipped_tars_are_extracted() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let archive = dir.path().join("template.tar.gz");
+    tar(
+        &template,
+        flate2::write::GzEncoder::new(
+            std::fs::File::create(&archive).unwrap(),
+            flate2::Compression::default(),
+        ),
+    );
+
+    scaffold_from(&archive, &dir.path().join("out"));
+}
+
+#[test]
+fn zips_are_extracted() {
+    let dir = TempDir::new().unwrap();
+    let archive = dir.path().join("template.zip");
+    zip(std::fs::File::create(&archive).unwrap());
+
+    scaffold_from(&archive, &dir.path().join("out"));
+}
+
+#[test]
+fn missing_archives_are_an_error() {
+    let dir = TempDir::new().unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(dir.path().join("missing.zip"))
+        .arg(dir.path().join("out"))
+        .args(["--select", "ts", "--yes"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("missing.zip"));
+}
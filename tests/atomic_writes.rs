@@ -0,0 +1,75 @@
+use std::path::PathBuf;
+
+use scaf::{write_file, File};
+use tempfile::TempDir;
+
+fn file(path: PathBuf, contents: &str) -> File {
+    File {
+        path,
+        source: PathBuf::from("template/script.sh"),
+        contents: contents.as_bytes().to_vec(),
+        depends_on: vec![],
+        mode: Some(0o755),
+        layer: 0,
+        raw: false,
+    }
+}
+
+#[test]
+fn renames_a_temp_file_into_place() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("bin/script.sh");
+    std::fs::create_dir(dir.path().join("bin")).unwrap();
+    std::fs::write(&path, "old").unwrap();
+
+    assert!(write_file(&file(path.clone(), "new")).unwrap());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+
+    // the temp file was the one that got renamed, so there's nothing else left next to it
+    let entries = std::fs::read_dir(dir.path().join("bin"))
+        .unwrap()
+        .map(|e| e.unwrap().file_name())
+        .collect::<Vec<_>>();
+    assert_eq!(entries, ["script.sh"]);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn executable_bit_comes_from_the_template() {
+    use scaf::{scaffold, Selections};
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = TempDir::new().unwrap();
+    let template = dir.path().join("template");
+    std::fs::create_dir_all(template.join("scripts")).unwrap();
+    std::fs::write(template.join("scaf.toml"), "[options]\n").unwrap();
+    let script = template.join("scripts/setup.sh");
+    std::fs::write(&script, "#!/bin/sh\n").unwrap();
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+    std::fs::write(template.join("README.md"), "").unwrap();
+    std::fs::set_permissions(
+        template.join("README.md"),
+        std::fs::Permissions::from_mode(0o644),
+    )
+    .unwrap();
+
+    let out = dir.path().join("out");
+    scaffold(&template, &out, &Selections::default()).unwrap();
+
+    let mode = |path: &str| {
+        std::fs::metadata(out.join(path))
+            .unwrap()
+            .permissions()
+            .mode()
+            & 0o777
+    };
+    assert_eq!(mode("scripts/setup.sh"), 0o755);
+    assert_eq!(mode("README.md"), 0o644);
+}
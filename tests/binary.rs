@@ -0,0 +1,26 @@
+use std::path::Path;
+
+use scaf::{scaffold, Selections};
+use tempfile::TempDir;
+
+#[test]
+fn binary_files_are_copied_byte_for_byte() {
+    let template = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/binary/template");
+    let source = template.join("{logo}assets/logo.png");
+    let original = std::fs::read(&source).unwrap();
+    // the fixture is only useful if it really isn't utf8
+    assert!(std::str::from_utf8(&original).is_err());
+
+    let dir = TempDir::new().unwrap();
+    let out = dir.path().join("out");
+    let selections = Selections {
+        options: vec![String::from("logo")],
+        variables: Default::default(),
+    };
+    scaffold(&template, &out, &selections).unwrap();
+
+    assert_eq!(
+        std::fs::read(out.join("assets/logo.png")).unwrap(),
+        original
+    );
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use std::collections::HashMap;
+
+use scaf::{error::ScafError, scaffold, Config, Selections};
+use tempfile::TempDir;
+
+/// a config with `variable` as the table for `db`
+fn config(variable: &str) -> String {
+    format!("[options]\na = \"A\"\n\n[variables.db]\n{}\n", variable)
+}
+
+const FILES: &[(&str, &str)] = &[
+    ("db{db=postgres}.sql", "-- postgres\n"),
+    ("db{db=sqlite}.sql", "-- sqlite\n"),
+    ("README.md", "uses {{db}}\n"),
+];
+
+const CHOICES: &str = r#"prompt = "Database"
+choices = ["postgres", "mysql", "sqlite"]"#;
+
+fn with_db(db: &str) -> Selections {
+    Selections {
+        options: vec![],
+        variables: HashMap::from([(String::from("db"), String::from(db))]),
+    }
+}
+
+#[test]
+fn chosen_value_is_used_in_paths_and_contents() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), &config(CHOICES), FILES);
+    let out = dir.path().join("out");
+
+    scaffold(&template, &out, &with_db("sqlite")).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(out.join("db.sql")).unwrap(),
+        "-- sqlite\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(out.join("README.md")).unwrap(),
+        "uses sqlite\n"
+    );
+}
+
+#[test]
+fn values_have_to_be_a_choice() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), &config(CHOICES), FILES);
+
+    match scaffold(&template, &dir.path().join("out"), &with_db("oracle")) {
+        Err(ScafError::InvalidChoice {
+            variable, value, ..
+        }) => {
+            assert_eq!(variable, "db");
+            assert_eq!(value, "oracle");
+        }
+        other => panic!("expected an invalid choice, got {:?}", other),
+    }
+}
+
+#[test]
+fn default_has_to_be_a_choice() {
+    let dir = TempDir::new().unwrap();
+    let config = config(&format!("{}\ndefault = \"oracle\"", CHOICES));
+    let template = common::template(dir.path(), &config, FILES);
+
+    let result = Config::from_base(&template, "scaf.toml");
+    assert!(matches!(result, Err(ScafError::InvalidChoice { .. })));
+}
@@ -0,0 +1,17 @@
+//! helpers shared by the integration tests
+
+use std::path::{Path, PathBuf};
+
+/// makes a template at `dir/template` with `config` as its `scaf.toml`, and `files` in it, key:
+/// path relative to the template, value: contents. directories get made as needed
+pub fn template(dir: &Path, config: &str, files: &[(&str, &str)]) -> PathBuf {
+    let template = dir.join("template");
+    std::fs::create_dir_all(&template).unwrap();
+    std::fs::write(template.join("scaf.toml"), config).unwrap();
+    for (path, contents) in files {
+        let path = template.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+    template
+}
@@ -0,0 +1,26 @@
+use std::process::Command;
+
+#[test]
+fn prints_a_script_for_each_shell() {
+    for shell in ["bash", "zsh", "fish"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+            .args(["completions", shell])
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}: {:?}", shell, output);
+        let script = String::from_utf8(output.stdout).unwrap();
+        assert!(!script.trim().is_empty(), "{} printed nothing", shell);
+        assert!(script.contains("scaf"), "{}: {}", shell, script);
+        // the subcommands get completed too
+        assert!(script.contains("new"), "{}: {}", shell, script);
+    }
+}
+
+#[test]
+fn unknown_shells_are_an_error() {
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .args(["completions", "cmd.exe"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
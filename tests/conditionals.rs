@@ -0,0 +1,50 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use scaf::{apply_conditionals, error::ScafError, File};
+
+fn file(contents: &str) -> File {
+    File {
+        path: PathBuf::from("out/main.rs"),
+        source: PathBuf::from("template/main.rs"),
+        contents: contents.as_bytes().to_vec(),
+        depends_on: vec![],
+        mode: None,
+        layer: 0,
+        raw: false,
+    }
+}
+
+fn apply(contents: &str, chosen: &[&str]) -> Result<String, ScafError> {
+    let mut files = [file(contents)];
+    let chosen = chosen.iter().map(|c| String::from(*c)).collect::<Vec<_>>();
+    apply_conditionals(&mut files, &chosen, &HashMap::new())?;
+    Ok(String::from_utf8(files[0].contents.clone()).unwrap())
+}
+
+const BOTH: &str = "start\n// scaf:if a, b\nboth\n// scaf:endif\nend\n";
+
+#[test]
+fn conditions_can_have_spaces_after_commas() {
+    assert_eq!(apply(BOTH, &["a", "b"]).unwrap(), "start\nboth\nend\n");
+    assert_eq!(apply(BOTH, &["a"]).unwrap(), "start\nend\n");
+}
+
+#[test]
+fn comment_closers_are_not_part_of_the_condition() {
+    let html = "<!-- scaf:if a -->\nhtml\n<!-- scaf:endif -->\n";
+    assert_eq!(apply(html, &["a"]).unwrap(), "html\n");
+    let css = "/* scaf:if !a */\ncss\n/* scaf:endif */\n";
+    assert_eq!(apply(css, &["a"]).unwrap(), "");
+}
+
+#[test]
+fn options_without_a_comma_are_an_error() {
+    let contents = "// scaf:if a b\nboth\n// scaf:endif\n";
+    match apply(contents, &["a", "b"]) {
+        Err(ScafError::Conditional { line, message, .. }) => {
+            assert_eq!(line, 1);
+            assert!(message.contains("separated with `,`"), "{}", message);
+        }
+        other => panic!("expected a conditional error, got {:?}", other),
+    }
+}
@@ -0,0 +1,17 @@
+use scaf::{error::ScafError, Config};
+use tempfile::TempDir;
+
+#[test]
+fn invalid_utf8_is_an_error() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("scaf.toml");
+    std::fs::write(&path, b"[options]\na = \"\xff\"\n".as_slice()).unwrap();
+
+    let e = Config::from_base(dir.path(), "scaf.toml").unwrap_err();
+    assert!(
+        matches!(&e, ScafError::ConfigNotUtf8 { path: p, offset: 15 } if *p == path),
+        "{:?}",
+        e
+    );
+    assert!(e.to_string().contains("offset 15"), "{}", e);
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use std::process::{Command, Stdio};
+
+use tempfile::TempDir;
+
+const CONFIG: &str = "[options]\na = \"A\"\n";
+const FILES: &[(&str, &str)] = &[("a.txt", "new\n")];
+
+/// runs `scaf new` in a pseudo terminal with `answer` typed in, returning everything it printed
+#[cfg(target_os = "linux")]
+fn in_terminal(
+    template: &std::path::Path,
+    out: &std::path::Path,
+    args: &str,
+    answer: &str,
+) -> String {
+    use std::io::Write;
+
+    let command = format!(
+        "{} new {} {} --select a {}",
+        env!("CARGO_BIN_EXE_scaf"),
+        template.display(),
+        out.display(),
+        args
+    );
+    let mut child = Command::new("script")
+        .args(["-qec", &command, "/dev/null"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(answer.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn no_question_without_a_terminal() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let out = dir.path().join("out");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(&template)
+        .arg(&out)
+        .args(["--select", "a", "--quiet"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(std::fs::read_to_string(out.join("a.txt")).unwrap(), "new\n");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn existing_files_are_reported_before_asking() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let out = dir.path().join("out");
+    std::fs::create_dir(&out).unwrap();
+    std::fs::write(out.join("a.txt"), "old\n").unwrap();
+
+    let printed = in_terminal(&template, &out, "--force", "y\n");
+    assert!(printed.contains("these files already exist"), "{}", printed);
+    assert!(!printed.contains("Continue?"), "{}", printed);
+    assert_eq!(std::fs::read_to_string(out.join("a.txt")).unwrap(), "old\n");
+
+    std::fs::write(out.join("a.txt"), "old\n").unwrap();
+    let printed = in_terminal(&template, &dir.path().join("other"), "", "y\n");
+    assert!(printed.contains("Continue?"), "{}", printed);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn saying_no_leaves_no_output_behind() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let out = dir.path().join("out");
+
+    let printed = in_terminal(&template, &out, "", "n\n");
+    assert!(printed.contains("aborted"), "{}", printed);
+    assert!(!out.exists());
+}
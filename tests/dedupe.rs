@@ -0,0 +1,138 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use scaf::{dedupe_files, Dependency, File};
+
+fn file(source: &str, contents: &str, depends_on: Vec<Dependency>) -> File {
+    at("out/README.md", source, contents, depends_on)
+}
+
+fn at(path: &str, source: &str, contents: &str, depends_on: Vec<Dependency>) -> File {
+    File {
+        path: PathBuf::from(path),
+        source: PathBuf::from(source),
+        contents: contents.as_bytes().to_vec(),
+        depends_on,
+        mode: None,
+        layer: 0,
+        raw: false,
+    }
+}
+
+#[test]
+fn files_with_the_same_path_are_equal() {
+    let plain = file("README.md", "plain", vec![]);
+    let ts = file(
+        "README{ts}.md",
+        "typescript",
+        vec![Dependency::Option(String::from("ts"))],
+    );
+    assert_eq!(plain, ts);
+}
+
+#[test]
+fn same_path_with_different_contents_has_one_winner() {
+    let files = vec![
+        file("README.md", "plain", vec![]),
+        file(
+            "README{ts}.md",
+            "typescript",
+            vec![Dependency::Option(String::from("ts"))],
+        ),
+    ];
+
+    let deduped = dedupe_files(files, &[String::from("ts")], &HashMap::new()).unwrap();
+    assert_eq!(deduped.len(), 1);
+    assert_eq!(deduped[0].contents, b"typescript");
+}
+
+/// the nested scan `dedupe_files` used to do, checking every file against every other one. returns
+/// the sources of the files that get kept, or `None` if there's a tie
+fn dedupe_by_scanning(files: &[File], chosen: &[String]) -> Option<Vec<PathBuf>> {
+    let files = files
+        .iter()
+        .filter(|f| {
+            f.depends_on
+                .iter()
+                .all(|d| d.is_met(chosen, &HashMap::new()))
+        })
+        .collect::<Vec<_>>();
+
+    let mut kept = vec![];
+    for f in &files {
+        let dups = files
+            .iter()
+            .filter(|f2| f2.path == f.path)
+            .collect::<Vec<_>>();
+        let max = dups.iter().map(|f| f.depends_on.len()).max().unwrap();
+        let maxes = dups
+            .iter()
+            .filter(|f| f.depends_on.len() == max)
+            .collect::<Vec<_>>();
+        if maxes.len() > 1 {
+            return None;
+        }
+        kept.push(maxes[0].source.clone());
+    }
+    kept.sort();
+    kept.dedup();
+    Some(kept)
+}
+
+/// a few hundred files spread over 100 paths, each path with files that depend on 0, 1, and 2
+/// options, and some that depend on an option that isn't chosen
+fn synthetic_files() -> Vec<File> {
+    let option = |i: usize| Dependency::Option(format!("o{}", i % 5));
+    let mut files = vec![];
+    for p in 0..100 {
+        let path = format!("out/file{}.txt", p);
+        for k in 0..3 {
+            let depends_on = (0..k).map(|j| option(p + j)).collect();
+            files.push(at(&path, &format!("{}-{}", p, k), "", depends_on));
+        }
+        if p % 7 == 0 {
+            let depends_on = vec![
+                option(p),
+                option(p + 1),
+                option(p + 2),
+                Dependency::Option(String::from("missing")),
+            ];
+            files.push(at(&path, &format!("{}-missing", p), "", depends_on));
+        }
+    }
+    files
+}
+
+#[test]
+fn grouping_keeps_the_same_files_as_scanning() {
+    let chosen = (0..5).map(|i| format!("o{}", i)).collect::<Vec<_>>();
+    let files = synthetic_files();
+
+    let mut kept = dedupe_files(files.clone(), &chosen, &HashMap::new())
+        .unwrap()
+        .into_iter()
+        .map(|f| f.source)
+        .collect::<Vec<_>>();
+    kept.sort();
+
+    assert_eq!(kept.len(), 100);
+    assert_eq!(Some(kept), dedupe_by_scanning(&files, &chosen));
+}
+
+#[test]
+fn grouping_finds_the_same_ties_as_scanning() {
+    let chosen = (0..5).map(|i| format!("o{}", i)).collect::<Vec<_>>();
+    let mut files = synthetic_files();
+    // another file as specific as the most specific one for a path
+    files.push(at(
+        "out/file42.txt",
+        "42-tie",
+        "",
+        vec![
+            Dependency::Option(String::from("o0")),
+            Dependency::Option(String::from("o1")),
+        ],
+    ));
+
+    assert_eq!(dedupe_by_scanning(&files, &chosen), None);
+    assert!(dedupe_files(files, &chosen, &HashMap::new()).is_err());
+}
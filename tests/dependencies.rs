@@ -0,0 +1,57 @@
+mod common;
+
+use std::{path::Path, process::Command};
+
+use tempfile::TempDir;
+
+const CONFIG: &str = "[options]\na = \"A\"\nb = \"B\"\n";
+
+fn dry_run(template: &Path) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(template)
+        .arg(template.with_file_name("out"))
+        .args(["--select", "a,b", "--dry-run"])
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn options_are_sorted() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(
+        dir.path(),
+        CONFIG,
+        &[("{b,a}one.txt", ""), ("{b|a}two.txt", "")],
+    );
+
+    let output = dry_run(&template);
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("one.txt (0 bytes), options: [a, b]"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("two.txt (0 bytes), options: [a|b]"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn reordered_options_are_the_same() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(
+        dir.path(),
+        CONFIG,
+        &[("{a,b}one.txt", ""), ("{b,a}one.txt", "")],
+    );
+
+    // neither is more specific, so they conflict instead of one winning by chance
+    let output = dry_run(&template);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(stderr.matches("depends on [a, b]").count(), 2, "{}", stderr);
+}
@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+
+use scaf::Opt;
+
+fn opt(description: &str) -> Opt {
+    toml::from_str::<HashMap<String, Opt>>(&format!(
+        "lint = {{ name = \"Linting\", description = {:?} }}",
+        description
+    ))
+    .unwrap()
+    .remove("lint")
+    .unwrap()
+}
+
+fn label(opt: &Opt, values: &HashMap<String, String>, width: usize) -> String {
+    console::strip_ansi_codes(&opt.label(values, width, 4)).to_string()
+}
+
+#[test]
+fn long_descriptions_wrap_to_the_width() {
+    let opt = opt("sets up eslint with a config that works for most projects");
+
+    assert_eq!(
+        label(&opt, &HashMap::new(), 30),
+        "Linting sets up eslint with a\n    config that works for most\n    projects"
+    );
+}
+
+#[test]
+fn newlines_in_descriptions_are_kept() {
+    let opt = opt("sets up eslint\nneeds node 18");
+
+    assert_eq!(
+        label(&opt, &HashMap::new(), 80),
+        "Linting sets up eslint\n    needs node 18"
+    );
+}
+
+#[test]
+fn descriptions_can_use_values() {
+    let opt = opt("sets up {{linter}} and {{missing}}");
+    let values = HashMap::from([(String::from("linter"), String::from("eslint"))]);
+
+    assert_eq!(
+        label(&opt, &values, 80),
+        "Linting sets up eslint and {{missing}}"
+    );
+}
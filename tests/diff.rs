@@ -0,0 +1,78 @@
+mod common;
+
+use std::process::Command;
+
+use tempfile::TempDir;
+
+#[test]
+fn diff_against_existing_project() {
+    let dir = TempDir::new().unwrap();
+    let template = dir.path().join("template");
+    let existing = dir.path().join("existing");
+    std::fs::create_dir_all(&template).unwrap();
+    std::fs::create_dir_all(&existing).unwrap();
+    std::fs::write(template.join("scaf.toml"), "[options]\na = \"A\"\n").unwrap();
+    std::fs::write(template.join("same.txt"), "same\n").unwrap();
+    std::fs::write(template.join("changed.txt"), "new\n").unwrap();
+    std::fs::write(template.join("added.txt"), "added\n").unwrap();
+    std::fs::write(existing.join("same.txt"), "same\n").unwrap();
+    std::fs::write(existing.join("changed.txt"), "old\n").unwrap();
+    std::fs::write(existing.join("removed.txt"), "removed\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(&template)
+        .arg("--diff")
+        .arg(&existing)
+        .args(["--select", "a"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("new file added.txt\n"), "{}", stdout);
+    assert!(stdout.contains("-old\n+new\n"), "{}", stdout);
+    assert!(stdout.contains("removed.txt"), "{}", stdout);
+    assert!(!stdout.contains("same.txt"), "{}", stdout);
+    // nothing was written
+    assert_eq!(
+        std::fs::read_to_string(existing.join("changed.txt")).unwrap(),
+        "old\n"
+    );
+    assert!(!existing.join("added.txt").exists());
+}
+
+#[test]
+fn binary_files_are_labelled_like_text_ones() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), "[options]\na = \"A\"\n", &[]);
+    let existing = dir.path().join("existing");
+    std::fs::create_dir_all(&existing).unwrap();
+    std::fs::write(template.join("added.bin"), b"\xff\xfe added").unwrap();
+    std::fs::write(template.join("changed.bin"), b"\xff\xfe new").unwrap();
+    std::fs::write(existing.join("changed.bin"), b"\xff\xfe old").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(&template)
+        .arg("--diff")
+        .arg(&existing)
+        .args(["--select", "a"])
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("new binary file added.bin\n"), "{}", stdout);
+    assert!(
+        stdout.contains("binary file changed.bin differs\n"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("added.bin differs"), "{}", stdout);
+}
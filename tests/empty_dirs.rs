@@ -0,0 +1,53 @@
+mod common;
+
+use std::{path::Path, process::Command};
+
+use tempfile::TempDir;
+
+const CONFIG: &str = "[options]\ndocs = \"Docs\"\nold = \"Old\"\n";
+/// with `docs` and without `old`, the `docs` directory ends up with nothing in it
+const FILES: &[(&str, &str)] = &[("{docs}docs/{old}notes.md", ""), ("README.md", "")];
+
+fn scaf_new(template: &Path, select: &str, extra: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(template)
+        .arg(template.with_file_name("out"))
+        .args(["--select", select, "--yes"])
+        .args(extra)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn empty_dirs_are_left_out_by_default() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+
+    let output = scaf_new(&template, "docs", &[]);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(dir.path().join("out/README.md").exists());
+    assert!(!dir.path().join("out/docs").exists());
+}
+
+#[test]
+fn keep_empty_dirs_makes_them() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+
+    let output = scaf_new(&template, "docs", &["--keep-empty-dirs"]);
+    assert!(output.status.success(), "{:?}", output);
+    let docs = dir.path().join("out/docs");
+    assert!(docs.is_dir());
+    assert_eq!(docs.read_dir().unwrap().count(), 0);
+}
+
+#[test]
+fn keep_empty_dirs_still_needs_the_option() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+
+    let output = scaf_new(&template, "old", &["--keep-empty-dirs"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(!dir.path().join("out/docs").exists());
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use std::process::Command;
+
+use scaf::{error::ScafError, scaffold, Selections};
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"exclusive_groups = [["npm", "yarn", "pnpm"]]
+
+[options]
+npm = "npm"
+yarn = "yarn"
+pnpm = "pnpm"
+"#;
+const FILES: &[(&str, &str)] = &[
+    ("{npm}package-lock.json", "{}\n"),
+    ("{yarn}yarn.lock", "\n"),
+];
+
+fn select(options: &[&str]) -> Selections {
+    Selections {
+        options: options.iter().map(|o| String::from(*o)).collect(),
+        variables: Default::default(),
+    }
+}
+
+#[test]
+fn one_option_from_a_group_can_be_chosen() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let out = dir.path().join("out");
+
+    scaffold(&template, &out, &select(&["npm"])).unwrap();
+    assert!(out.join("package-lock.json").exists());
+    assert!(!out.join("yarn.lock").exists());
+}
+
+#[test]
+fn two_options_from_a_group_are_an_error() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let out = dir.path().join("out");
+
+    match scaffold(&template, &out, &select(&["yarn", "npm"])) {
+        Err(ScafError::ExclusiveOptions { group, chosen }) => {
+            assert_eq!(group, ["npm", "yarn", "pnpm"]);
+            assert_eq!(chosen, ["npm", "yarn"]);
+        }
+        other => panic!("expected an exclusive group error, got {:?}", other),
+    }
+    assert!(!out.exists());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(&template)
+        .arg(&out)
+        .args(["--select", "npm,yarn", "--yes"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("only one of [npm, yarn, pnpm] can be chosen, but npm, yarn were"),
+        "{}",
+        stderr
+    );
+}
@@ -0,0 +1,66 @@
+use std::process::Command;
+
+use tempfile::TempDir;
+
+#[test]
+fn lists_the_files_each_option_adds() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("scaf.toml"),
+        "[options]\nts = \"TypeScript\"\neslint = \"ESLint\"\ndocker = \"Docker\"\n",
+    )
+    .unwrap();
+    std::fs::create_dir(dir.path().join("{ts}src")).unwrap();
+    std::fs::write(dir.path().join("{ts}src/index.ts"), "").unwrap();
+    std::fs::write(dir.path().join("{ts|eslint}.eslintrc"), "").unwrap();
+    std::fs::write(dir.path().join("{!ts}index.js"), "").unwrap();
+    std::fs::write(dir.path().join("README.md"), "").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(dir.path())
+        .arg("--explain")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout,
+        "docker Docker\n  no files\neslint ESLint\n  {ts|eslint}.eslintrc\nts TypeScript\n  {ts|eslint}.eslintrc\n  {ts}src/index.ts\n"
+    );
+}
+
+#[test]
+fn explain_skips_groups_left_out_files_by_option() {
+    let dir = TempDir::new().unwrap();
+    let template = dir.path().join("template");
+    std::fs::create_dir(&template).unwrap();
+    std::fs::write(
+        template.join("scaf.toml"),
+        "[options]\nts = \"TypeScript\"\neslint = \"ESLint\"\ndocker = \"Docker\"\n",
+    )
+    .unwrap();
+    std::fs::write(template.join("{ts}index.ts"), "").unwrap();
+    std::fs::write(template.join("{!ts}index.js"), "").unwrap();
+    std::fs::write(template.join("{eslint,docker}lint.Dockerfile"), "").unwrap();
+    std::fs::write(template.join("{docker}Dockerfile"), "").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(&template)
+        .arg(dir.path().join("out"))
+        .args(["--explain-skips", "--dry-run", "--select", "ts"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.starts_with(
+            "files left out for the chosen options:\n  !ts\n    {!ts}index.js\n  docker\n    {docker}Dockerfile\n    {eslint,docker}lint.Dockerfile\n  eslint\n    {eslint,docker}lint.Dockerfile\n"
+        ),
+        "{}",
+        stdout
+    );
+}
@@ -0,0 +1 @@
+react
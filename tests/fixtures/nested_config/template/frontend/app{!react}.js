@@ -0,0 +1 @@
+vanilla
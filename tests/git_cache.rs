@@ -0,0 +1,155 @@
+use std::{path::Path, process::Command};
+
+use tempfile::TempDir;
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=scaf", "-c", "user.email=scaf@example.com"])
+        .args(args)
+        .output()
+        .unwrap()
+        .status;
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+/// a git repo with a template in it, committed once
+fn repo(dir: &Path) -> std::path::PathBuf {
+    let repo = dir.join("repo");
+    init(&repo, "first\n");
+    repo
+}
+
+/// makes `repo` a git repo with a template whose `file.txt` has `contents`
+fn init(repo: &Path, contents: &str) {
+    std::fs::create_dir_all(repo).unwrap();
+    git(repo, &["init", "--quiet", "--initial-branch", "main"]);
+    std::fs::write(repo.join("scaf.toml"), "[options]\na = \"A\"\n").unwrap();
+    std::fs::write(repo.join("file.txt"), contents).unwrap();
+    git(repo, &["add", "-A"]);
+    git(repo, &["commit", "--quiet", "-m", "first"]);
+}
+
+fn scaf_new(dir: &Path, url: &str, out: &str, extra: &[&str]) -> String {
+    let out = dir.join(out);
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .env("XDG_CACHE_HOME", dir.join("cache"))
+        .args(["new", url])
+        .arg(&out)
+        .args(["--select", "a", "--yes", "--quiet"])
+        .args(extra)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    std::fs::read_to_string(out.join("file.txt")).unwrap()
+}
+
+#[test]
+fn branches_are_fetched_again() {
+    let dir = TempDir::new().unwrap();
+    let repo = repo(dir.path());
+    let url = format!("file://{}", repo.display());
+
+    assert_eq!(scaf_new(dir.path(), &url, "one", &[]), "first\n");
+    assert!(
+        dir.path()
+            .join("cache/scaf/git")
+            .read_dir()
+            .unwrap()
+            .count()
+            == 1
+    );
+
+    std::fs::write(repo.join("file.txt"), "second\n").unwrap();
+    git(&repo, &["commit", "--quiet", "-am", "second"]);
+    assert_eq!(scaf_new(dir.path(), &url, "two", &[]), "second\n");
+}
+
+#[test]
+fn broken_caches_get_cloned_again() {
+    let dir = TempDir::new().unwrap();
+    let repo = repo(dir.path());
+    let url = format!("file://{}", repo.display());
+
+    scaf_new(dir.path(), &url, "one", &[]);
+    let cached = dir
+        .path()
+        .join("cache/scaf/git")
+        .read_dir()
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    std::fs::remove_dir_all(cached.join(".git/objects")).unwrap();
+
+    assert_eq!(scaf_new(dir.path(), &url, "two", &[]), "first\n");
+}
+
+#[test]
+fn no_cache_leaves_the_cache_alone() {
+    let dir = TempDir::new().unwrap();
+    let repo = repo(dir.path());
+    let url = format!("file://{}", repo.display());
+
+    assert_eq!(
+        scaf_new(dir.path(), &url, "one", &["--no-cache"]),
+        "first\n"
+    );
+    assert!(!dir.path().join("cache/scaf").exists());
+}
+
+#[test]
+fn similar_urls_get_their_own_clones() {
+    let dir = TempDir::new().unwrap();
+    // these used to turn into the same cache directory
+    init(&dir.path().join("repos/a/b"), "a/b\n");
+    init(&dir.path().join("repos/a_b"), "a_b\n");
+    let nested = format!("file://{}", dir.path().join("repos/a/b").display());
+    let flat = format!("file://{}", dir.path().join("repos/a_b").display());
+
+    assert_eq!(scaf_new(dir.path(), &nested, "one", &[]), "a/b\n");
+    assert_eq!(scaf_new(dir.path(), &flat, "two", &[]), "a_b\n");
+    assert_eq!(
+        dir.path()
+            .join("cache/scaf/git")
+            .read_dir()
+            .unwrap()
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn clones_of_other_urls_are_not_reused() {
+    let dir = TempDir::new().unwrap();
+    let repo = repo(dir.path());
+    let url = format!("file://{}", repo.display());
+    scaf_new(dir.path(), &url, "one", &[]);
+
+    let cached = dir
+        .path()
+        .join("cache/scaf/git")
+        .read_dir()
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let other = dir.path().join("other");
+    init(&other, "other\n");
+    git(
+        &cached,
+        &["remote", "set-url", "origin", other.to_str().unwrap()],
+    );
+
+    assert_eq!(scaf_new(dir.path(), &url, "two", &[]), "first\n");
+    let origin = Command::new("git")
+        .arg("-C")
+        .arg(&cached)
+        .args(["config", "--get", "remote.origin.url"])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&origin.stdout).trim(), url);
+}
@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+
+use scaf::{scaffold, Selections};
+use tempfile::TempDir;
+
+// one test, since it changes the environment git runs in for the whole process
+#[test]
+fn defaults_can_come_from_git_config() {
+    let dir = TempDir::new().unwrap();
+    let gitconfig = dir.path().join("gitconfig");
+    std::fs::write(&gitconfig, "[scaftest]\n\tauthor = Jane Doe\n").unwrap();
+    std::env::set_var("GIT_CONFIG_GLOBAL", &gitconfig);
+    std::env::set_var("GIT_CONFIG_NOSYSTEM", "1");
+
+    let template = dir.path().join("template");
+    std::fs::create_dir(&template).unwrap();
+    std::fs::write(
+        template.join("scaf.toml"),
+        r#"[options]
+a = "A"
+
+[variables.author]
+prompt = "Author"
+default = "git:scaftest.author"
+
+[variables.email]
+prompt = "Email"
+default = "git:scaftest.missing"
+
+[variables.license]
+prompt = "License"
+default = "MIT"
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        template.join("LICENSE"),
+        "{{license}} by {{author}} <{{email}}>\n",
+    )
+    .unwrap();
+
+    let out = dir.path().join("out");
+    let selections = Selections {
+        options: vec![],
+        variables: HashMap::new(),
+    };
+    scaffold(&template, &out, &selections).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(out.join("LICENSE")).unwrap(),
+        "MIT by Jane Doe <>\n"
+    );
+}
@@ -0,0 +1,28 @@
+use scaf::Config;
+use tempfile::TempDir;
+
+#[test]
+fn options_in_a_group_are_kept_together() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("scaf.toml"),
+        r#"order = ["ts", "docker"]
+
+[options]
+ts = { name = "TypeScript", group = "Language" }
+docker = "Docker"
+eslint = { name = "ESLint", group = "Linting" }
+rust = { name = "Rust", group = "Language" }
+prettier = { name = "Prettier", group = "Linting" }
+"#,
+    )
+    .unwrap();
+
+    let config = Config::from_base(dir.path(), "scaf.toml").unwrap();
+    let keys = config
+        .visible_options()
+        .into_iter()
+        .map(|(k, _)| k.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(keys, ["ts", "rust", "eslint", "prettier", "docker"]);
+}
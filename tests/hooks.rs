@@ -0,0 +1,93 @@
+//! the hooks are shell commands, so these only run on unix
+#![cfg(unix)]
+
+mod common;
+
+use std::{path::Path, process::Command};
+
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"pre_hooks = ["echo $SCAF_OPTIONS > options.txt", "pwd > pre.txt"]
+post_hooks = ["test -f a.txt && pwd > post.txt"]
+
+[options]
+a = "A"
+b = "B"
+"#;
+const FILES: &[(&str, &str)] = &[("{a}a.txt", "a\n"), ("{b}b.txt", "b\n")];
+
+fn scaf_new(template: &Path, extra: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(template)
+        .arg(template.with_file_name("out"))
+        .args(["--select", "b,a", "--yes"])
+        .args(extra)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn hooks_run_inside_of_the_output() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+
+    let output = scaf_new(&template, &[]);
+    assert!(output.status.success(), "{:?}", output);
+    let out = dir.path().join("out").canonicalize().unwrap();
+    for hook in ["pre.txt", "post.txt"] {
+        let pwd = std::fs::read_to_string(out.join(hook)).unwrap();
+        assert_eq!(
+            Path::new(pwd.trim()).canonicalize().unwrap(),
+            out,
+            "{}",
+            hook
+        );
+    }
+}
+
+#[test]
+fn hooks_get_the_chosen_options() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+
+    let output = scaf_new(&template, &[]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("out/options.txt")).unwrap(),
+        "b,a\n"
+    );
+}
+
+#[test]
+fn failing_hooks_stop_scaffolding() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(
+        dir.path(),
+        "pre_hooks = [\"exit 4\"]\npost_hooks = [\"touch post.txt\"]\n\n[options]\na = \"A\"\nb = \"B\"\n",
+        FILES,
+    );
+
+    let output = scaf_new(&template, &[]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("hook `exit 4` failed"), "{}", stderr);
+    let out = dir.path().join("out");
+    assert!(!out.join("a.txt").exists());
+    assert!(!out.join("post.txt").exists());
+}
+
+#[test]
+fn no_hooks_and_dry_run_skip_them() {
+    for flag in ["--no-hooks", "--dry-run"] {
+        let dir = TempDir::new().unwrap();
+        let template = common::template(dir.path(), CONFIG, FILES);
+
+        let output = scaf_new(&template, &[flag]);
+        assert!(output.status.success(), "{}: {:?}", flag, output);
+        let out = dir.path().join("out");
+        for hook in ["options.txt", "pre.txt", "post.txt"] {
+            assert!(!out.join(hook).exists(), "{} ran with {}", hook, flag);
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use std::{path::Path, process::Command};
+
+use scaf::{error::ScafError, match_ignoring_case, Config};
+use tempfile::TempDir;
+
+fn config(dir: &Path, options: &str) -> Config {
+    std::fs::write(dir.join("scaf.toml"), format!("[options]\n{}", options)).unwrap();
+    Config::from_base(dir, "scaf.toml").unwrap()
+}
+
+fn keys(keys: &[&str]) -> Vec<String> {
+    keys.iter().map(|k| String::from(*k)).collect()
+}
+
+#[test]
+fn keys_resolve_to_the_option_they_match() {
+    let dir = TempDir::new().unwrap();
+    let config = config(
+        dir.path(),
+        "typescript = \"TypeScript\"\nESLint = \"ESLint\"\n",
+    );
+
+    let matched = match_ignoring_case(&config, &keys(&["TypeScript", "eslint"])).unwrap();
+    assert_eq!(matched, ["typescript", "ESLint"]);
+}
+
+#[test]
+fn exact_matches_win() {
+    let dir = TempDir::new().unwrap();
+    let config = config(
+        dir.path(),
+        "ts = \"TypeScript\"\nTS = \"Also TypeScript\"\n",
+    );
+
+    let matched = match_ignoring_case(&config, &keys(&["TS"])).unwrap();
+    assert_eq!(matched, ["TS"]);
+}
+
+#[test]
+fn keys_matching_more_than_one_option_are_ambiguous() {
+    let dir = TempDir::new().unwrap();
+    let config = config(
+        dir.path(),
+        "ts = \"TypeScript\"\nTS = \"Also TypeScript\"\n",
+    );
+
+    match match_ignoring_case(&config, &keys(&["Ts"])) {
+        Err(ScafError::AmbiguousOption { given, matches }) => {
+            assert_eq!(given, "Ts");
+            assert_eq!(matches, ["TS", "ts"]);
+        }
+        other => panic!("expected an ambiguous option, got {:?}", other),
+    }
+}
+
+#[test]
+fn keys_only_ignore_case_with_the_flag() {
+    let dir = TempDir::new().unwrap();
+    let template = dir.path().join("template");
+    std::fs::create_dir(&template).unwrap();
+    config(&template, "typescript = \"TypeScript\"\n");
+    std::fs::write(template.join("{typescript}index.ts"), "").unwrap();
+
+    let scaf = |extra: &[&str], out: &str| {
+        Command::new(env!("CARGO_BIN_EXE_scaf"))
+            .args(["new", "-y", "-q", "--select", "TypeScript"])
+            .args(extra)
+            .arg(&template)
+            .arg(dir.path().join(out))
+            .output()
+            .unwrap()
+    };
+
+    assert!(!scaf(&[], "strict").status.success());
+    let output = scaf(&["--ignore-case"], "ignored");
+    assert!(output.status.success(), "{:?}", output);
+    assert!(dir.path().join("ignored/index.ts").exists());
+}
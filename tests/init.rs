@@ -0,0 +1,39 @@
+use std::{path::Path, process::Command};
+
+use tempfile::TempDir;
+
+fn scaf(args: &[&str], dir: &Path) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .args(args)
+        .arg(dir)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn init_makes_a_template_that_lints() {
+    let dir = TempDir::new().unwrap();
+    let template = dir.path().join("template");
+
+    let output = scaf(&["init"], &template);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(template.join("scaf.toml").exists());
+    assert!(template.join(".scafignore").exists());
+
+    let output = scaf(&["lint"], &template);
+    assert!(output.status.success(), "{:?}", output);
+}
+
+#[test]
+fn init_needs_force_for_non_empty_directories() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("notes.txt"), "").unwrap();
+
+    let output = scaf(&["init"], dir.path());
+    assert!(!output.status.success());
+    assert!(!dir.path().join("scaf.toml").exists());
+
+    let output = scaf(&["init", "--force"], dir.path());
+    assert!(output.status.success(), "{:?}", output);
+    assert!(dir.path().join("notes.txt").exists());
+}
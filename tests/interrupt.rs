@@ -0,0 +1,59 @@
+//! sends a real SIGINT, so these only run on unix
+#![cfg(unix)]
+
+mod common;
+
+use std::{
+    os::unix::process::CommandExt,
+    process::{Command, Stdio},
+    time::{Duration, Instant},
+};
+
+use tempfile::TempDir;
+
+/// the hook says when it started, then waits to be interrupted
+const CONFIG: &str = "pre_hooks = [\"touch ../started && sleep 10\"]\n\n[options]\na = \"A\"\n";
+const FILES: &[(&str, &str)] = &[("a.txt", "a\n")];
+
+#[test]
+fn ctrl_c_cleans_up_extracted_archives() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let archive = dir.path().join("template.tar");
+    let mut builder = tar::Builder::new(std::fs::File::create(&archive).unwrap());
+    builder.append_dir_all(".", &template).unwrap();
+    builder.finish().unwrap();
+    let tmp = dir.path().join("tmp");
+    std::fs::create_dir(&tmp).unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .env("TMPDIR", &tmp)
+        .arg("new")
+        .arg(&archive)
+        .arg(dir.path().join("out"))
+        .args(["--select", "a", "--yes"])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        // its own process group, so the hook gets the ctrl-c too like it would in a terminal
+        .process_group(0)
+        .spawn()
+        .unwrap();
+
+    let start = Instant::now();
+    while !dir.path().join("started").exists() {
+        assert!(start.elapsed() < Duration::from_secs(10), "hook never ran");
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    // the archive is extracted into `tmp` while scaf runs
+    assert_eq!(tmp.read_dir().unwrap().count(), 1);
+    let status = Command::new("kill")
+        .args(["-s", "INT", "--"])
+        .arg(format!("-{}", child.id()))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    assert_eq!(child.wait().unwrap().code(), Some(130));
+    assert_eq!(tmp.read_dir().unwrap().count(), 0);
+    assert!(!dir.path().join("out/a.txt").exists());
+}
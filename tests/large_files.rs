@@ -0,0 +1,91 @@
+mod common;
+
+use std::{path::Path, process::Command};
+
+use tempfile::TempDir;
+
+const CONFIG: &str = "[options]\na = \"A\"\n";
+const FILES: &[(&str, &str)] = &[("small.txt", "small\n")];
+
+fn scaf_new(template: &Path, args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(template)
+        .arg(template.with_file_name("out"))
+        .args(["--select", "a", "--yes"])
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// a template with `big.bin` in it that's `size` bytes
+fn with_big_file(dir: &Path, size: u64) -> std::path::PathBuf {
+    let template = common::template(dir, CONFIG, FILES);
+    std::fs::File::create(template.join("big.bin"))
+        .unwrap()
+        .set_len(size)
+        .unwrap();
+    template
+}
+
+#[test]
+fn files_over_10mb_are_an_error_by_default() {
+    let dir = TempDir::new().unwrap();
+    let template = with_big_file(dir.path(), 10 * 1024 * 1024 + 1);
+
+    let output = scaf_new(&template, &["--dry-run"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("big.bin is 10485761 bytes"), "{}", stderr);
+    assert!(stderr.contains("--max-file-size"), "{}", stderr);
+}
+
+#[test]
+fn the_limit_can_be_raised() {
+    let dir = TempDir::new().unwrap();
+    let template = with_big_file(dir.path(), 2048);
+
+    assert!(!scaf_new(&template, &["--max-file-size", "1K"])
+        .status
+        .success());
+    let output = scaf_new(&template, &["--max-file-size", "2kb"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        std::fs::metadata(dir.path().join("out/big.bin"))
+            .unwrap()
+            .len(),
+        2048
+    );
+}
+
+#[test]
+fn skip_large_leaves_big_files_out() {
+    let dir = TempDir::new().unwrap();
+    let template = with_big_file(dir.path(), 2048);
+
+    let output = scaf_new(&template, &["--max-file-size", "1K", "--skip-large"]);
+    assert!(output.status.success(), "{:?}", output);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("skipping"), "{}", stderr);
+    assert!(stderr.contains("big.bin, it's 2048 bytes"), "{}", stderr);
+    assert!(dir.path().join("out/small.txt").exists());
+    assert!(!dir.path().join("out/big.bin").exists());
+}
+
+#[test]
+fn bad_sizes_are_an_error() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+
+    for (size, message) in [
+        ("lots", "expected a size like `10MB`"),
+        ("10TB", "expected a size like `10MB`"),
+        ("1.5M", "expected a size like `10MB`"),
+        ("99999999999G", "too large"),
+    ] {
+        let output = scaf_new(&template, &["--dry-run", "--max-file-size", size]);
+        assert_eq!(output.status.code(), Some(2), "{}: {:?}", size, output);
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains(message), "{}: {}", size, stderr);
+    }
+}
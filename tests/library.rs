@@ -0,0 +1,43 @@
+mod common;
+
+use std::collections::HashMap;
+
+use scaf::{error::ScafError, scaffold, Selections};
+use tempfile::TempDir;
+
+const CONFIG: &str = "[options]\nts = \"TypeScript\"\n\n[variables.name]\nprompt = \"Name\"\n";
+const FILES: &[(&str, &str)] = &[
+    ("index{ts}.ts", "// {{name}}\n"),
+    ("index{!ts}.js", "// {{name}}\n"),
+];
+
+#[test]
+fn scaffolds_without_prompting() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let out = dir.path().join("out");
+    let selections = Selections {
+        options: vec![String::from("ts")],
+        variables: HashMap::from([(String::from("name"), String::from("app"))]),
+    };
+
+    let written = scaffold(&template, &out, &selections).unwrap();
+    assert_eq!(written, vec![out.join("index.ts")]);
+    assert_eq!(
+        std::fs::read_to_string(out.join("index.ts")).unwrap(),
+        "// app\n"
+    );
+}
+
+#[test]
+fn variables_without_defaults_need_a_value() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let selections = Selections {
+        options: vec![],
+        variables: HashMap::new(),
+    };
+
+    let result = scaffold(&template, &dir.path().join("out"), &selections);
+    assert!(matches!(result, Err(ScafError::MissingVariables(v)) if v == ["name"]));
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use std::process::Command;
+
+use tempfile::TempDir;
+
+const CONFIG: &str = "[options]\nts = \"TypeScript\"\ndocker = \"Docker\"\n";
+/// choosing both `ts` and `docker` leaves two files tied for each of two paths
+const FILES: &[(&str, &str)] = &[
+    ("{ts}README.md", ""),
+    ("{docker}README.md", ""),
+    ("{ts}a.txt", ""),
+    ("{docker}a.txt", ""),
+];
+
+#[test]
+fn lint_reports_every_conflict_as_json() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .args(["lint", "--format", "json"])
+        .arg(&template)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+
+    let problems: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let problems = problems.as_array().unwrap();
+    assert_eq!(problems.len(), 2);
+    for (problem, path) in problems.iter().zip(["README.md", "a.txt"]) {
+        assert_eq!(problem["kind"], "conflict");
+        assert_eq!(problem["path"], template.join(path).to_str().unwrap());
+        assert_eq!(problem["options"], serde_json::json!(["docker", "ts"]));
+        assert_eq!(problem["files"].as_array().unwrap().len(), 2);
+    }
+}
+
+#[test]
+fn scaffolding_reports_every_conflict() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .args(["new", "-y", "-q", "--select", "ts,docker"])
+        .arg(&template)
+        .arg(dir.path().join("out"))
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("README.md:"), "{}", stderr);
+    assert!(stderr.contains("a.txt:"), "{}", stderr);
+}
+
+const VALUE_CONFIG: &str = "[options]\ndocker = \"Docker\"\n\n[variables.db]\nprompt = \"Database\"\ndefault = \"sqlite\"\n";
+/// `db.sql` only has a tie when `docker` is chosen and `db` is `postgres`, which isn't its default
+const VALUE_FILES: &[(&str, &str)] = &[
+    ("db{db=postgres}.sql", ""),
+    ("{docker}db.sql", ""),
+    ("README.md", ""),
+];
+
+#[test]
+fn lint_tries_every_choice_of_a_variable() {
+    let dir = TempDir::new().unwrap();
+    let config = format!(
+        "{}choices = [\"sqlite\", \"postgres\", \"mysql\"]\n",
+        VALUE_CONFIG
+    );
+    let template = common::template(dir.path(), &config, VALUE_FILES);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("lint")
+        .arg(&template)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(
+            "db.sql: can't choose between files when [docker] are chosen and db=postgres:"
+        ),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn value_conflicts_are_in_the_json_report() {
+    // without `choices`, the values the files check for are the ones that get tried
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), VALUE_CONFIG, VALUE_FILES);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .args(["lint", "--format", "json"])
+        .arg(&template)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+
+    let problems: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let problems = problems.as_array().unwrap();
+    assert_eq!(problems.len(), 1, "{:?}", problems);
+    let problem = &problems[0];
+    assert_eq!(problem["kind"], "conflict");
+    assert_eq!(problem["path"], template.join("db.sql").to_str().unwrap());
+    assert_eq!(problem["options"], serde_json::json!(["docker"]));
+    assert_eq!(problem["values"], serde_json::json!({ "db": "postgres" }));
+    let depends_on = problem["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["depends_on"].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        depends_on,
+        [
+            serde_json::json!(["db=postgres"]),
+            serde_json::json!(["docker"])
+        ]
+    );
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use std::path::Path;
+
+use scaf::{error::ScafError, scaffold, Config, Selections};
+use tempfile::TempDir;
+
+/// a README, and a LICENSE next to the template instead of in it
+const FILES: &[(&str, &str)] = &[("README.md", "# readme\n"), ("../LICENSE", "MIT\n")];
+
+fn written(template: &Path, out: &Path) -> Vec<String> {
+    let selections = Selections {
+        options: vec![],
+        variables: Default::default(),
+    };
+    let mut written = scaffold(template, out, &selections)
+        .unwrap()
+        .iter()
+        .map(|p| p.strip_prefix(out).unwrap().to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    written.sort();
+    written
+}
+
+#[test]
+fn listing_files_replaces_scanning() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(
+        dir.path(),
+        "[files]\n\"../LICENSE\" = \"LICENSE.txt\"\n",
+        FILES,
+    );
+    let out = dir.path().join("out");
+
+    assert_eq!(written(&template, &out), ["LICENSE.txt"]);
+    assert_eq!(
+        std::fs::read_to_string(out.join("LICENSE.txt")).unwrap(),
+        "MIT\n"
+    );
+}
+
+#[test]
+fn scan_adds_the_template_directory_back() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(
+        dir.path(),
+        "scan = true\n\n[files]\n\"../LICENSE\" = \"LICENSE\"\n",
+        FILES,
+    );
+
+    assert_eq!(
+        written(&template, &dir.path().join("out")),
+        ["LICENSE", "README.md"]
+    );
+}
+
+#[test]
+fn listed_files_have_to_exist() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), "[files]\n\"../COPYING\" = \"COPYING\"\n", FILES);
+
+    let result = Config::from_base(&template, "scaf.toml");
+    assert!(matches!(result, Err(ScafError::InvalidConfig(m)) if m.contains("COPYING")));
+}
+
+#[test]
+fn listed_files_cant_go_outside_of_the_output() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(
+        dir.path(),
+        "[files]\n\"../LICENSE\" = \"../LICENSE\"\n",
+        FILES,
+    );
+
+    let result = Config::from_base(&template, "scaf.toml");
+    assert!(matches!(result, Err(ScafError::InvalidConfig(_))));
+}
@@ -0,0 +1,67 @@
+use std::process::Command;
+
+use tempfile::TempDir;
+
+#[test]
+fn manifest_leaves_out_skipped_files() {
+    let dir = TempDir::new().unwrap();
+    let template = dir.path().join("template");
+    std::fs::create_dir(&template).unwrap();
+    std::fs::write(template.join("scaf.toml"), "[options]\na = \"A\"\n").unwrap();
+    std::fs::write(template.join("new.txt"), "new\n").unwrap();
+    std::fs::write(template.join("old.txt"), "new\n").unwrap();
+
+    let out = dir.path().join("out");
+    std::fs::create_dir(&out).unwrap();
+    std::fs::write(out.join("old.txt"), "old\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(&template)
+        .arg(&out)
+        .args(["--select", "a", "--yes", "--force"])
+        .args(["--overwrite-policy", "skip", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+
+    let manifest: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let files = manifest["files"].as_array().unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0]["path"], out.join("new.txt").to_str().unwrap());
+    assert_eq!(
+        std::fs::read_to_string(out.join("old.txt")).unwrap(),
+        "old\n"
+    );
+}
+
+#[test]
+fn stats_count_every_file() {
+    let dir = TempDir::new().unwrap();
+    let template = dir.path().join("template");
+    std::fs::create_dir(&template).unwrap();
+    std::fs::write(template.join("scaf.toml"), "[options]\na = \"A\"\n").unwrap();
+    std::fs::write(template.join("new.txt"), "new\n").unwrap();
+    std::fs::write(template.join("old.txt"), "new\n").unwrap();
+    std::fs::write(template.join("{!a}left_out.txt"), "").unwrap();
+
+    let out = dir.path().join("out");
+    std::fs::create_dir(&out).unwrap();
+    std::fs::write(out.join("old.txt"), "old\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(&template)
+        .arg(&out)
+        .args(["--select", "a", "--yes", "--force"])
+        .args(["--overwrite-policy", "skip", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+
+    let manifest: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        manifest["stats"],
+        serde_json::json!({ "written": 1, "left_out": 1, "merged": 0, "skipped": 1, "bytes": 4 })
+    );
+}
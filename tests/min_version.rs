@@ -0,0 +1,51 @@
+mod common;
+
+use scaf::{error::ScafError, Config};
+use tempfile::TempDir;
+
+fn config_with(min: &str) -> Result<Config, ScafError> {
+    let dir = TempDir::new().unwrap();
+    let config = format!("min_scaf_version = \"{}\"\n\n[options]\na = \"A\"\n", min);
+    let template = common::template(dir.path(), &config, &[]);
+    Config::from_base(&template, "scaf.toml")
+}
+
+#[test]
+fn newer_versions_tell_you_to_upgrade() {
+    let e = config_with("999.0.0").unwrap_err();
+    assert!(
+        matches!(&e, ScafError::TooOld { required, .. } if required.major == 999),
+        "{:?}",
+        e
+    );
+    let message = e.to_string();
+    assert!(
+        message.contains("needs scaf 999.0.0 or newer"),
+        "{}",
+        message
+    );
+    assert!(
+        message.contains(&format!("this is scaf {}", env!("CARGO_PKG_VERSION"))),
+        "{}",
+        message
+    );
+    assert!(message.contains("upgrade"), "{}", message);
+}
+
+#[test]
+fn older_or_equal_versions_are_fine() {
+    config_with("0.0.1").unwrap();
+    config_with(env!("CARGO_PKG_VERSION")).unwrap();
+}
+
+#[test]
+fn unparsable_versions_are_an_error() {
+    let e = config_with("soon").unwrap_err();
+    assert!(matches!(e, ScafError::InvalidConfig(_)), "{:?}", e);
+    assert!(
+        e.to_string()
+            .contains("min_scaf_version `soon` isn't a version"),
+        "{}",
+        e
+    );
+}
@@ -0,0 +1,181 @@
+use std::{path::Path, process::Command};
+
+use tempfile::TempDir;
+
+fn write(path: &Path, contents: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, contents).unwrap();
+}
+
+fn scaf_new(args: &[&Path], select: &str, extra: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .args(args)
+        .args(["--select", select, "--yes", "--quiet"])
+        .args(extra)
+        .output()
+        .unwrap()
+}
+
+/// two templates that both have a `.gitignore`
+fn gitignore_templates(dir: &Path) -> (std::path::PathBuf, std::path::PathBuf) {
+    let base = dir.join("base");
+    let node = dir.join("node");
+    write(&base.join("scaf.toml"), "[options]\n");
+    write(&base.join(".gitignore"), "target\n");
+    write(&base.join("README.md"), "base\n");
+    write(&node.join("scaf.toml"), "[options]\n");
+    write(&node.join(".gitignore"), "node_modules");
+    (base, node)
+}
+
+#[test]
+fn later_templates_win() {
+    let dir = TempDir::new().unwrap();
+    let base = dir.path().join("base");
+    let react = dir.path().join("react");
+    write(&base.join("scaf.toml"), "[options]\nts = \"TypeScript\"\n");
+    write(&base.join("README.md"), "base\n");
+    // more specific than react's README, but react still wins since it comes later
+    write(&base.join("{ts}README.md"), "base ts\n");
+    write(&base.join("{ts}src/index.ts"), "index\n");
+    write(
+        &react.join("scaf.toml"),
+        "[options]\nts = \"TypeScript\"\nreact = \"React\"\n",
+    );
+    write(&react.join("README.md"), "react\n");
+    write(&react.join("{react}src/App.tsx"), "app\n");
+
+    let out = dir.path().join("out");
+    let output = scaf_new(
+        &[&base, &react, &out],
+        "ts,react",
+        &["--merge-strategy", "override"],
+    );
+    assert!(output.status.success(), "{:?}", output);
+
+    assert_eq!(
+        std::fs::read_to_string(out.join("README.md")).unwrap(),
+        "react\n"
+    );
+    assert!(out.join("src/index.ts").exists());
+    assert!(out.join("src/App.tsx").exists());
+}
+
+#[test]
+fn options_have_to_match() {
+    let dir = TempDir::new().unwrap();
+    let base = dir.path().join("base");
+    let other = dir.path().join("other");
+    write(&base.join("scaf.toml"), "[options]\nts = \"TypeScript\"\n");
+    write(&other.join("scaf.toml"), "[options]\nts = \"TS\"\n");
+
+    let output = scaf_new(&[&base, &other, &dir.path().join("out")], "ts", &[]);
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("different in more than one template: ts")
+    );
+}
+
+#[test]
+fn same_path_errors_by_default() {
+    let dir = TempDir::new().unwrap();
+    let (base, node) = gitignore_templates(dir.path());
+    let out = dir.path().join("out");
+
+    let output = scaf_new(&[&base, &node, &out], "", &[]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--merge-strategy"), "{}", stderr);
+    assert!(stderr.contains(".gitignore"), "{}", stderr);
+    assert!(!out.exists());
+}
+
+#[test]
+fn concat_joins_in_template_order() {
+    let dir = TempDir::new().unwrap();
+    let (base, node) = gitignore_templates(dir.path());
+    let out = dir.path().join("out");
+
+    let output = scaf_new(&[&node, &base, &out], "", &["--merge-strategy", "concat"]);
+    assert!(output.status.success(), "{:?}", output);
+
+    // node's doesn't end in a newline, so one goes in between
+    assert_eq!(
+        std::fs::read_to_string(out.join(".gitignore")).unwrap(),
+        "node_modules\ntarget\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(out.join("README.md")).unwrap(),
+        "base\n"
+    );
+}
+
+#[test]
+fn override_uses_the_later_template() {
+    let dir = TempDir::new().unwrap();
+    let (base, node) = gitignore_templates(dir.path());
+    let out = dir.path().join("out");
+
+    let output = scaf_new(&[&base, &node, &out], "", &["--merge-strategy", "override"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        std::fs::read_to_string(out.join(".gitignore")).unwrap(),
+        "node_modules"
+    );
+}
+
+#[test]
+fn merged_files_are_not_counted_as_left_out() {
+    let dir = TempDir::new().unwrap();
+    let (base, node) = gitignore_templates(dir.path());
+    let scaf = |out: &str, extra: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+            .arg("new")
+            .args([&base, &node, &dir.path().join(out)])
+            .args(["--select", "", "--yes", "--merge-strategy", "concat"])
+            .args(extra)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{:?}", output);
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    let stdout = scaf("text", &[]);
+    assert!(stdout.contains("Scaffolded 2 files"), "{}", stdout);
+    assert!(
+        stdout.contains("0 left out, 1 merged, 0 skipped since they exist"),
+        "{}",
+        stdout
+    );
+
+    let manifest: serde_json::Value =
+        serde_json::from_str(&scaf("json", &["--format", "json"])).unwrap();
+    assert_eq!(manifest["stats"]["written"], 2);
+    assert_eq!(manifest["stats"]["left_out"], 0);
+    assert_eq!(manifest["stats"]["merged"], 1);
+}
+
+#[test]
+fn merge_strategies_are_listed() {
+    let help = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .args(["new", "--help"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(help.stdout).unwrap();
+    for strategy in ["override:", "concat:", "error:"] {
+        assert!(stdout.contains(strategy), "{}", stdout);
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .args(["new", "a", "b", "--merge-strategy", "newest"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("[possible values: override, concat, error]"),
+        "{}",
+        stderr
+    );
+}
@@ -0,0 +1,42 @@
+use std::process::Command;
+
+use tempfile::TempDir;
+
+#[test]
+fn parent_directories_are_made_for_nested_files() {
+    let dir = TempDir::new().unwrap();
+    let template = dir.path().join("template");
+    std::fs::create_dir_all(template.join("src/components")).unwrap();
+    std::fs::write(template.join("scaf.toml"), "[options]\n").unwrap();
+    std::fs::write(template.join("src/index.ts"), "index\n").unwrap();
+    // lots of files in the same directory, which get written at the same time
+    for i in 0..50 {
+        std::fs::write(
+            template.join(format!("src/components/c{}.ts", i)),
+            format!("{}\n", i),
+        )
+        .unwrap();
+    }
+
+    let out = dir.path().join("out");
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(&template)
+        .arg(&out)
+        .args(["--select", "", "--yes", "--quiet"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+
+    assert!(out.join("src/components").is_dir());
+    assert_eq!(
+        std::fs::read_to_string(out.join("src/index.ts")).unwrap(),
+        "index\n"
+    );
+    for i in 0..50 {
+        assert_eq!(
+            std::fs::read_to_string(out.join(format!("src/components/c{}.ts", i))).unwrap(),
+            format!("{}\n", i)
+        );
+    }
+}
@@ -0,0 +1,30 @@
+use std::collections::HashMap;
+
+use scaf::{scaffold, Selections};
+use tempfile::TempDir;
+
+#[test]
+fn lowercases_names_but_not_variables() {
+    let dir = TempDir::new().unwrap();
+    let template = dir.path().join("template");
+    std::fs::create_dir_all(template.join("SRC")).unwrap();
+    std::fs::write(
+        template.join("scaf.toml"),
+        "normalize_filenames = \"lower\"\n\n[options]\na = \"A\"\n\n[variables.Name]\nprompt = \"Name\"\n",
+    )
+    .unwrap();
+    std::fs::write(template.join("SRC/Main{Name}.RS"), "").unwrap();
+    std::fs::write(template.join("README{a}.md"), "").unwrap();
+
+    let out = dir.path().join("out");
+    let selections = Selections {
+        options: vec![String::from("a")],
+        variables: HashMap::from([(String::from("Name"), String::from("App"))]),
+    };
+    let mut written = scaffold(&template, &out, &selections).unwrap();
+    written.sort();
+    assert_eq!(
+        written,
+        vec![out.join("readme.md"), out.join("src/mainApp.rs")]
+    );
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use std::{path::Path, process::Command};
+
+use tempfile::TempDir;
+
+const CONFIG: &str = "[options]\na = \"A\"\nb = \"B\"\n";
+/// nothing without `a`
+const FILES: &[(&str, &str)] = &[("{a}a.txt", "a")];
+
+fn scaf(template: &Path, out: &Path, args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(template)
+        .arg(out)
+        .arg("--yes")
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn warns_without_making_the_output() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let out = dir.path().join("out");
+
+    let output = scaf(&template, &out, &["--select", ""]);
+    assert!(output.status.success(), "{:?}", output);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("no options were chosen"), "{}", stderr);
+    assert!(!out.exists());
+}
+
+#[test]
+fn errors_with_strict() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let out = dir.path().join("out");
+
+    let output = scaf(&template, &out, &["--select", "b", "--strict"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("doesn't have any files without options"),
+        "{}",
+        stderr
+    );
+    assert!(!out.exists());
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use std::{path::Path, process::Command};
+
+use tempfile::TempDir;
+
+const CONFIG: &str = "[options]\nci = \"CI\"\n";
+const FILES: &[(&str, &str)] = &[
+    ("{ci}ci/workflows/test.yml", ""),
+    ("src/main.rs", ""),
+    ("README.md", ""),
+];
+
+fn dry_run(template: &Path, args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(template)
+        .arg(template.with_file_name("out"))
+        .arg("--dry-run")
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn only_scaffolds_files_inside_the_path() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+
+    // the output path and the template path both work
+    for only in ["ci", "./{ci}ci"] {
+        let output = dry_run(&template, &["--select", "ci", "--only", only]);
+        assert!(output.status.success(), "{:?}", output);
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(stdout.contains("ci/workflows/test.yml"), "{}", stdout);
+        assert!(!stdout.contains("main.rs"), "{}", stdout);
+        assert!(!stdout.contains("README.md"), "{}", stdout);
+    }
+}
+
+#[test]
+fn paths_without_files_warn() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+
+    // ci isn't chosen, so there aren't any files in it
+    let output = dry_run(
+        &template,
+        &["--select", "", "--only", "ci", "--only", "src"],
+    );
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("src/main.rs"), "{}", stdout);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("--only ci doesn't have any files in it"),
+        "{}",
+        stderr
+    );
+}
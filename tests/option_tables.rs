@@ -0,0 +1,55 @@
+use scaf::{error::ScafError, Config};
+use tempfile::TempDir;
+
+fn config(contents: &str) -> Result<Config, ScafError> {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("scaf.toml"), contents).unwrap();
+    Config::from_base(dir.path(), "scaf.toml")
+}
+
+#[test]
+fn tables_are_options_too() {
+    let config = config(
+        r#"[options]
+docker = "Docker"
+
+[[option]]
+key = "ts"
+name = "TypeScript"
+description = "types"
+default = true
+group = "Language"
+
+[[option]]
+key = "eslint"
+name = "ESLint"
+requires = ["ts"]
+"#,
+    )
+    .unwrap();
+
+    let mut all = config.get_all_options();
+    all.sort();
+    assert_eq!(all, ["docker", "eslint", "ts"]);
+
+    let ts = &config.options["ts"];
+    assert_eq!(ts.name, "TypeScript");
+    assert_eq!(ts.description.as_deref(), Some("types"));
+    assert_eq!(ts.group.as_deref(), Some("Language"));
+    assert_eq!(config.options["eslint"].requires, ["ts"]);
+    assert_eq!(config.defaults, ["ts"]);
+}
+
+#[test]
+fn keys_cant_be_in_both_forms() {
+    let result = config(
+        r#"[options]
+ts = "TypeScript"
+
+[[option]]
+key = "ts"
+name = "TypeScript"
+"#,
+    );
+    assert!(matches!(result, Err(ScafError::InvalidConfig(_))));
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use std::process::Command;
+
+use scaf::Config;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"order = ["zebra", "mango"]
+
+[meta]
+title = "Fruit stand"
+description = "a template for selling fruit"
+
+[options]
+apple = "Apple"
+mango = "Mango"
+cherry = "Cherry"
+zebra = "Zebra"
+"#;
+
+#[test]
+fn ordered_options_come_first_then_the_rest_by_name() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, &[]);
+    let config = Config::from_base(&template, "scaf.toml").unwrap();
+
+    let keys = config
+        .visible_options()
+        .into_iter()
+        .map(|(k, _)| k.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(keys, ["zebra", "mango", "apple", "cherry"]);
+}
+
+#[test]
+fn meta_is_parsed_and_listed() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, &[]);
+    let config = Config::from_base(&template, "scaf.toml").unwrap();
+    assert_eq!(config.meta.title.as_deref(), Some("Fruit stand"));
+    assert_eq!(
+        config.meta.description.as_deref(),
+        Some("a template for selling fruit")
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(&template)
+        .arg("--list")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines = stdout.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "Fruit stand");
+    assert_eq!(lines[1], "a template for selling fruit");
+    assert!(lines[3..].iter().any(|l| l.contains("Mango")), "{}", stdout);
+}
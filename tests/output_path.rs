@@ -0,0 +1,128 @@
+mod common;
+
+use std::{path::Path, process::Command};
+
+use tempfile::TempDir;
+
+const CONFIG: &str = "[options]\na = \"A\"\n";
+const FILES: &[(&str, &str)] = &[("file.txt", "hello\n")];
+
+fn scaf_new(template: &Path, output: &Path) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(template)
+        .arg(output)
+        .args(["--select", "a", "--yes"])
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn output_same_as_template() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+
+    let output = scaf_new(&template, &template);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("can't be inside of the template"));
+}
+
+#[test]
+fn output_nested_in_template() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+
+    let output = scaf_new(&template, &template.join("out/nested"));
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("can't be inside of the template"));
+    assert!(!template.join("out").exists());
+
+    // a relative path that only ends up inside after `..` is resolved
+    let output = scaf_new(&template, &dir.path().join("other/../template/out"));
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("can't be inside of the template"));
+}
+
+#[test]
+fn output_next_to_template() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    // shares a prefix with the template as a string, but not as a path
+    let out = dir.path().join("template-out");
+
+    let output = scaf_new(&template, &out);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        std::fs::read_to_string(out.join("file.txt")).unwrap(),
+        "hello\n"
+    );
+}
+
+#[test]
+fn output_defaults_to_the_template_name() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let work = dir.path().join("work");
+    std::fs::create_dir(&work).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .current_dir(&work)
+        .arg("new")
+        .arg(&template)
+        .args(["--select", "a", "--yes"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        std::fs::read_to_string(work.join("template/file.txt")).unwrap(),
+        "hello\n"
+    );
+}
+
+#[test]
+fn only_path_is_the_output_inside_of_a_template() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .current_dir(&template)
+        .args(["new", "../out", "--select", "a", "--yes"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("out/file.txt")).unwrap(),
+        "hello\n"
+    );
+}
+
+#[test]
+fn current_template_is_used_without_any_paths() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .current_dir(&template)
+        .args(["new", "--select", "a", "--yes"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("out/file.txt")).unwrap(),
+        "hello\n"
+    );
+}
+
+#[test]
+fn only_path_is_still_the_template_outside_of_one() {
+    let dir = TempDir::new().unwrap();
+    common::template(dir.path(), CONFIG, FILES);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .current_dir(dir.path())
+        .args(["new", "missing", "--select", "a", "--yes"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("missing"));
+}
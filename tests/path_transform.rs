@@ -0,0 +1,113 @@
+//! the scripts are shell scripts, so these only run on unix
+#![cfg(unix)]
+
+mod common;
+
+use std::{os::unix::fs::PermissionsExt, path::Path, process::Command};
+
+use scaf::{error::ScafError, scaffold, Selections};
+use tempfile::TempDir;
+
+/// a template using `script` as its path_transform
+fn with_script(dir: &Path, script: &str) -> std::path::PathBuf {
+    let script = format!("#!/bin/sh\n{}\n", script);
+    let template = common::template(
+        dir,
+        "path_transform = \"transform.sh\"\n",
+        &[("transform.sh", &script), ("src/main.rs", "fn main() {}\n")],
+    );
+    std::fs::set_permissions(
+        template.join("transform.sh"),
+        std::fs::Permissions::from_mode(0o755),
+    )
+    .unwrap();
+    template
+}
+
+fn run(template: &Path, out: &Path) -> Result<Vec<String>, ScafError> {
+    let mut written = scaffold(template, out, &Selections::default())?
+        .iter()
+        .map(|p| p.strip_prefix(out).unwrap().to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    written.sort();
+    Ok(written)
+}
+
+#[test]
+fn paths_go_where_the_script_says() {
+    let dir = TempDir::new().unwrap();
+    let template = with_script(dir.path(), "read path\necho \"generated/$path\"");
+
+    let written = run(&template, &dir.path().join("out")).unwrap();
+    assert_eq!(written, ["generated/src/main.rs"]);
+}
+
+#[test]
+fn failing_scripts_are_an_error() {
+    let dir = TempDir::new().unwrap();
+    let template = with_script(dir.path(), "exit 3");
+
+    let result = run(&template, &dir.path().join("out"));
+    assert!(matches!(result, Err(ScafError::PathTransform { .. })));
+}
+
+#[test]
+fn paths_the_script_prints_cant_leave_the_output() {
+    let dir = TempDir::new().unwrap();
+    let template = with_script(dir.path(), "echo ../escaped");
+
+    let result = run(&template, &dir.path().join("out"));
+    assert!(matches!(result, Err(ScafError::OutsideOutput { .. })));
+    assert!(!dir.path().join("escaped").exists());
+}
+
+#[test]
+fn scripts_can_print_more_than_a_pipe_holds() {
+    let dir = TempDir::new().unwrap();
+    // the spaces get trimmed off, but they have to be read before the script can exit
+    let template = with_script(
+        dir.path(),
+        "read path\nprintf '%200000s' ''\necho \"generated/$path\"",
+    );
+
+    let written = run(&template, &dir.path().join("out")).unwrap();
+    assert_eq!(written, ["generated/src/main.rs"]);
+}
+
+#[test]
+fn no_hooks_and_dry_run_dont_run_the_script() {
+    let dir = TempDir::new().unwrap();
+    let ran = dir.path().join("ran");
+    let template = with_script(
+        dir.path(),
+        &format!(
+            "touch {}\nread path\necho \"generated/$path\"",
+            ran.display()
+        ),
+    );
+    let out = dir.path().join("out");
+    let scaf = |flag: &str| {
+        Command::new(env!("CARGO_BIN_EXE_scaf"))
+            .arg("new")
+            .arg(&template)
+            .arg(&out)
+            .args(["--select", "", "--yes", flag])
+            .output()
+            .unwrap()
+    };
+
+    let output = scaf("--dry-run");
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("src/main.rs"), "{}", stdout);
+    assert!(!stdout.contains("generated"), "{}", stdout);
+
+    let output = scaf("--no-hooks");
+    assert!(output.status.success(), "{:?}", output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("with --no-hooks"), "{}", stderr);
+    assert!(out.join("src/main.rs").exists());
+    assert!(!out.join("generated").exists());
+
+    assert!(!ran.exists());
+}
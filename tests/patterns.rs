@@ -0,0 +1,106 @@
+mod common;
+
+use std::{collections::HashMap, process::Command};
+
+use scaf::{error::ScafError, scaffold, Config, Selections};
+use tempfile::TempDir;
+
+/// a config with `variable` as the table for `package_name`
+fn config(variable: &str) -> String {
+    format!("[variables.package_name]\n{}\n", variable)
+}
+
+const FILES: &[(&str, &str)] = &[("Cargo.toml", "name = \"{{package_name}}\"\n")];
+
+const NO_SPACES: &str = r#"prompt = "Package name"
+pattern = "[^ ]+""#;
+
+fn with_name(name: &str) -> Selections {
+    Selections {
+        options: vec![],
+        variables: HashMap::from([(String::from("package_name"), String::from(name))]),
+    }
+}
+
+#[test]
+fn matching_values_are_used() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), &config(NO_SPACES), FILES);
+    let out = dir.path().join("out");
+
+    scaffold(&template, &out, &with_name("my-app")).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(out.join("Cargo.toml")).unwrap(),
+        "name = \"my-app\"\n"
+    );
+}
+
+#[test]
+fn values_have_to_match_all_of_the_pattern() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), &config(NO_SPACES), FILES);
+
+    match scaffold(&template, &dir.path().join("out"), &with_name("my app")) {
+        Err(ScafError::PatternMismatch {
+            variable, value, ..
+        }) => {
+            assert_eq!(variable, "package_name");
+            assert_eq!(value, "my app");
+        }
+        other => panic!("expected a pattern mismatch, got {:?}", other),
+    }
+}
+
+#[test]
+fn set_values_that_dont_match_error_with_the_message() {
+    let dir = TempDir::new().unwrap();
+    let config = config(&format!(
+        "{}\npattern_message = \"package names can't have spaces\"",
+        NO_SPACES
+    ));
+    let template = common::template(dir.path(), &config, FILES);
+    let out = dir.path().join("out");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .args([
+            "new",
+            "-y",
+            "-q",
+            "--select",
+            "",
+            "--set",
+            "package_name=my app",
+        ])
+        .arg(&template)
+        .arg(&out)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("package names can't have spaces"),
+        "{}",
+        stderr
+    );
+    assert!(!out.exists());
+}
+
+#[test]
+fn default_has_to_match() {
+    let dir = TempDir::new().unwrap();
+    let config = config(&format!("{}\ndefault = \"my app\"", NO_SPACES));
+    let template = common::template(dir.path(), &config, FILES);
+
+    let result = Config::from_base(&template, "scaf.toml");
+    assert!(matches!(result, Err(ScafError::PatternMismatch { .. })));
+}
+
+#[test]
+fn invalid_patterns_are_caught_when_loading() {
+    let dir = TempDir::new().unwrap();
+    let config = config("prompt = \"Package name\"\npattern = \"[a-z\"");
+    let template = common::template(dir.path(), &config, FILES);
+
+    let result = Config::from_base(&template, "scaf.toml");
+    assert!(matches!(result, Err(ScafError::InvalidConfig(_))));
+}
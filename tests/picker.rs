@@ -0,0 +1,56 @@
+//! the picker needs a terminal, which `script` makes
+#![cfg(target_os = "linux")]
+
+mod common;
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use tempfile::TempDir;
+
+/// more options than fit without the search box. `docker`'s description mentions postgres, but its
+/// name doesn't
+fn config() -> String {
+    let mut config = String::from(
+        "[options]\ndocker = { name = \"Docker\", description = \"runs postgres in a container\" }\npostgres = \"Postgres\"\n",
+    );
+    for i in 0..12 {
+        config.push_str(&format!("extra{} = \"Extra {}\"\n", i, i));
+    }
+    config
+}
+const FILES: &[(&str, &str)] = &[("{docker}docker.txt", ""), ("{postgres}postgres.txt", "")];
+
+#[test]
+fn typing_only_matches_option_names() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), &config(), FILES);
+
+    let command = format!(
+        "{} new {} {} --dry-run",
+        env!("CARGO_BIN_EXE_scaf"),
+        template.display(),
+        dir.path().join("out").display(),
+    );
+    let mut child = Command::new("script")
+        .args(["-qec", &command, "/dev/null"])
+        .env("NO_COLOR", "1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // the first match gets toggled
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"postgres \r")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    let printed = String::from_utf8_lossy(&output.stdout);
+
+    assert!(printed.contains("postgres.txt"), "{}", printed);
+    assert!(!printed.contains("docker.txt"), "{}", printed);
+}
@@ -0,0 +1,147 @@
+mod common;
+
+use std::{path::Path, process::Command};
+
+use tempfile::TempDir;
+
+const CONFIG: &str =
+    "[options]\nts = \"TypeScript\"\ndocker = \"Docker\"\n\n[variables.name]\nprompt = \"Name\"\n";
+const FILES: &[(&str, &str)] = &[
+    ("index{ts}.ts", "// {{name}}\n"),
+    ("index{!ts}.js", "// {{name}}\n"),
+    ("{docker}Dockerfile", "FROM {{name}}\n"),
+];
+
+fn scaf_new(template: &Path, out: &Path, args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(template)
+        .arg(out)
+        .args(["--yes", "--quiet"])
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// every file in `dir` and what's in it, to compare two outputs
+fn contents(dir: &Path) -> Vec<(String, String)> {
+    let mut files = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .map(|p| {
+            (
+                p.file_name().unwrap().to_string_lossy().to_string(),
+                std::fs::read_to_string(&p).unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+#[test]
+fn the_lock_file_has_what_was_chosen() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let out = dir.path().join("out");
+
+    let output = scaf_new(
+        &template,
+        &out,
+        &["--select", "ts,docker", "--set", "name=app"],
+    );
+    assert!(output.status.success(), "{:?}", output);
+    let lock: toml::Value =
+        toml::from_str(&std::fs::read_to_string(out.join(".scaf-lock.toml")).unwrap()).unwrap();
+    assert_eq!(
+        lock["options"],
+        toml::Value::Array(vec!["docker".into(), "ts".into()])
+    );
+    assert_eq!(lock["variables"]["name"].as_str(), Some("app"));
+}
+
+#[test]
+fn replaying_makes_the_same_output() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let first = dir.path().join("first");
+    let second = dir.path().join("second");
+
+    let output = scaf_new(
+        &template,
+        &first,
+        &["--select", "ts,docker", "--set", "name=app"],
+    );
+    assert!(output.status.success(), "{:?}", output);
+    let lock = first.join(".scaf-lock.toml");
+    let output = scaf_new(&template, &second, &["--replay", lock.to_str().unwrap()]);
+    assert!(output.status.success(), "{:?}", output);
+
+    assert_eq!(contents(&first), contents(&second));
+}
+
+#[test]
+fn set_wins_over_a_replayed_value() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let first = dir.path().join("first");
+    let second = dir.path().join("second");
+
+    scaf_new(&template, &first, &["--select", "ts", "--set", "name=app"]);
+    let lock = first.join(".scaf-lock.toml");
+    let output = scaf_new(
+        &template,
+        &second,
+        &["--replay", lock.to_str().unwrap(), "--set", "name=other"],
+    );
+    assert!(output.status.success(), "{:?}", output);
+
+    assert_eq!(
+        std::fs::read_to_string(second.join("index.ts")).unwrap(),
+        "// other\n"
+    );
+}
+
+#[test]
+fn stale_locks_are_an_error() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let lock = dir.path().join("old.toml");
+    std::fs::write(
+        &lock,
+        "options = [\"ts\", \"rust\"]\n\n[variables]\nname = \"app\"\nlicense = \"MIT\"\n",
+    )
+    .unwrap();
+
+    let out = dir.path().join("out");
+    let output = scaf_new(&template, &out, &["--replay", lock.to_str().unwrap()]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains(
+            "has options or variables that aren't in the template anymore: license, rust"
+        ),
+        "{}",
+        stderr
+    );
+    assert!(!out.exists());
+}
+
+#[test]
+fn old_locks_with_a_seed_still_replay() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let lock = dir.path().join("old.toml");
+    std::fs::write(
+        &lock,
+        "options = [\"ts\"]\nseed = 42\n\n[variables]\nname = \"app\"\n",
+    )
+    .unwrap();
+
+    let out = dir.path().join("out");
+    let output = scaf_new(&template, &out, &["--replay", lock.to_str().unwrap()]);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(!std::fs::read_to_string(out.join(".scaf-lock.toml"))
+        .unwrap()
+        .contains("seed"));
+}
@@ -0,0 +1,166 @@
+mod common;
+
+use std::{collections::HashMap, path::Path};
+
+use scaf::{error::ScafError, scaffold, Config, Selections};
+use tempfile::TempDir;
+
+fn load(config: &str) -> Result<Config, ScafError> {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("scaf.toml"), config).unwrap();
+    Config::from_base(dir.path(), "scaf.toml")
+}
+
+#[test]
+fn requiring_both_sides_of_an_exclusive_group() {
+    let result = load(
+        r#"
+        exclusive_groups = [["npm", "yarn"]]
+
+        [options]
+        npm = "npm"
+        yarn = "yarn"
+        lockfiles = { name = "Lockfiles", requires = ["npm", "yarn"] }
+        "#,
+    );
+
+    match result {
+        Err(ScafError::ContradictoryRequires {
+            option,
+            conflicting,
+        }) => {
+            assert_eq!(option, "lockfiles");
+            assert_eq!(conflicting, ["npm", "yarn"]);
+        }
+        other => panic!("expected a contradiction, got {:?}", other),
+    }
+}
+
+#[test]
+fn requiring_an_excluded_option() {
+    // a requires b, but they're in an exclusive group together
+    let result = load(
+        r#"
+        exclusive_groups = [["a", "b"]]
+
+        [options]
+        a = { name = "A", requires = ["b"] }
+        b = "B"
+        "#,
+    );
+
+    assert!(matches!(
+        result,
+        Err(ScafError::ContradictoryRequires { option, .. }) if option == "a"
+    ));
+}
+
+#[test]
+fn requires_cycle() {
+    let result = load(
+        r#"
+        [options]
+        a = { name = "A", requires = ["b"] }
+        b = { name = "B", requires = ["c"] }
+        c = { name = "C", requires = ["a"] }
+        "#,
+    );
+
+    match result {
+        Err(ScafError::RequiresCycle(cycle)) => assert_eq!(cycle, ["a", "b", "c", "a"]),
+        other => panic!("expected a cycle, got {:?}", other),
+    }
+}
+
+#[test]
+fn requires_unknown_option() {
+    let result = load(
+        r#"
+        [options]
+        a = { name = "A", requires = ["nope"] }
+        "#,
+    );
+
+    assert!(matches!(result, Err(ScafError::InvalidConfig(_))));
+}
+
+#[test]
+fn consistent_requires() {
+    let result = load(
+        r#"
+        exclusive_groups = [["npm", "yarn"]]
+
+        [options]
+        npm = "npm"
+        yarn = "yarn"
+        eslint = { name = "ESLint", requires = ["npm"] }
+        "#,
+    );
+
+    assert!(result.is_ok(), "{:?}", result.err());
+}
+
+/// c requires b, which requires a. each one gates a file
+fn chain(dir: &Path, extra: &str) -> std::path::PathBuf {
+    let config = format!(
+        r#"{}
+        [options]
+        a = "A"
+        b = {{ name = "B", requires = ["a"] }}
+        c = {{ name = "C", requires = ["b"] }}
+        "#,
+        extra
+    );
+    common::template(
+        dir,
+        &config,
+        &[("{a}a.txt", ""), ("{b}b.txt", ""), ("{c}c.txt", "")],
+    )
+}
+
+fn choose(options: &[&str]) -> Selections {
+    Selections {
+        options: options.iter().map(|o| String::from(*o)).collect(),
+        variables: HashMap::new(),
+    }
+}
+
+#[test]
+fn requirements_are_chosen_transitively() {
+    let dir = TempDir::new().unwrap();
+    let template = chain(dir.path(), "");
+    let out = dir.path().join("out");
+
+    let written = scaffold(&template, &out, &choose(&["c"])).unwrap();
+    assert_eq!(
+        written,
+        vec![out.join("a.txt"), out.join("b.txt"), out.join("c.txt")]
+    );
+}
+
+#[test]
+fn requirements_gated_below_nothing_stay_out() {
+    let dir = TempDir::new().unwrap();
+    let template = chain(dir.path(), "");
+    let out = dir.path().join("out");
+
+    let written = scaffold(&template, &out, &choose(&["b"])).unwrap();
+    assert_eq!(written, vec![out.join("a.txt"), out.join("b.txt")]);
+}
+
+#[test]
+fn missing_requirements_error_without_add_required() {
+    let dir = TempDir::new().unwrap();
+    let template = chain(dir.path(), "add_required = false");
+    let out = dir.path().join("out");
+
+    match scaffold(&template, &out, &choose(&["c", "b"])) {
+        Err(ScafError::MissingRequires(missing)) => {
+            assert_eq!(missing, [(String::from("b"), String::from("a"))]);
+        }
+        other => panic!("expected missing requires, got {:?}", other),
+    }
+
+    let written = scaffold(&template, &out, &choose(&["c", "b", "a"])).unwrap();
+    assert_eq!(written.len(), 3);
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use std::{path::Path, process::Command};
+
+use tempfile::TempDir;
+
+const CONFIG: &str = "[options]\na = \"A\"\n";
+const FILES: &[(&str, &str)] = &[
+    ("a.txt", "new\n"),
+    ("b.txt", "new\n"),
+    ("sub/c.txt", "new\n"),
+];
+
+/// a template where `b.txt` can't be written, since the output already has a directory there
+fn setup() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    common::template(dir.path(), CONFIG, FILES);
+
+    let out = dir.path().join("out");
+    std::fs::create_dir_all(out.join("b.txt")).unwrap();
+    std::fs::write(out.join("a.txt"), "old\n").unwrap();
+    dir
+}
+
+fn scaf_new(dir: &Path, extra: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(dir.join("template"))
+        .arg(dir.join("out"))
+        .args(["--select", "a", "--yes", "--force", "--overwrite"])
+        .args(extra)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn failed_writes_are_rolled_back() {
+    let dir = setup();
+    let out = dir.path().join("out");
+
+    let output = scaf_new(dir.path(), &[]);
+    assert!(!output.status.success());
+    assert_eq!(std::fs::read_to_string(out.join("a.txt")).unwrap(), "old\n");
+    assert!(!out.join("sub").exists());
+    assert!(out.join("b.txt").is_dir());
+}
+
+#[test]
+fn no_rollback_leaves_written_files() {
+    let dir = setup();
+    let out = dir.path().join("out");
+
+    let output = scaf_new(dir.path(), &["--no-rollback"]);
+    assert!(!output.status.success());
+    assert_eq!(std::fs::read_to_string(out.join("a.txt")).unwrap(), "new\n");
+    assert!(out.join("sub/c.txt").exists());
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use tempfile::TempDir;
+
+fn scaf(args: &[&str], stdin: &str) -> std::process::Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+const CONFIG: &str = "[options]\nts = \"TypeScript\"\neslint = \"ESLint\"\ndocker = \"Docker\"\n";
+const FILES: &[(&str, &str)] = &[
+    ("{ts}index.ts", ""),
+    ("{eslint}.eslintrc", ""),
+    ("{docker}Dockerfile", ""),
+];
+
+#[test]
+fn options_are_read_from_stdin() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let output_path = dir.path().join("out");
+
+    let output = scaf(
+        &[
+            "new",
+            "-y",
+            "-q",
+            "--select-stdin",
+            template.to_str().unwrap(),
+            output_path.to_str().unwrap(),
+        ],
+        "# generated\n  ts  \n\neslint\n",
+    );
+    assert!(output.status.success(), "{:?}", output);
+    assert!(output_path.join("index.ts").exists());
+    assert!(output_path.join(".eslintrc").exists());
+    assert!(!output_path.join("Dockerfile").exists());
+}
+
+#[test]
+fn options_from_stdin_are_validated() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let output_path = dir.path().join("out");
+
+    let output = scaf(
+        &[
+            "new",
+            "-y",
+            "-q",
+            "--select-stdin",
+            template.to_str().unwrap(),
+            output_path.to_str().unwrap(),
+        ],
+        "ts\nrust\n",
+    );
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("rust"));
+    assert!(!output_path.exists());
+}
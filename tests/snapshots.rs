@@ -0,0 +1,117 @@
+//! every directory in `tests/fixtures` has a `template` to scaffold, an `args` file with the extra
+//! arguments to pass to `scaf new` (one per line), and the `expected` output. the output has to
+//! match `expected` byte for byte, and a dry run has to print the same thing every time
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use tempfile::TempDir;
+
+/// every file under `dir`, key: path relative to `dir`, value: contents
+fn read_tree(dir: &Path) -> BTreeMap<PathBuf, Vec<u8>> {
+    let mut files = BTreeMap::new();
+    let mut dirs = vec![dir.to_path_buf()];
+
+    while let Some(d) = dirs.pop() {
+        for entry in std::fs::read_dir(&d).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else {
+                let contents = std::fs::read(&path).unwrap();
+                files.insert(path.strip_prefix(dir).unwrap().to_path_buf(), contents);
+            }
+        }
+    }
+
+    files
+}
+
+/// what `scaf new --dry-run` prints for the fixture, which lists every file it would write
+fn dry_run(fixture: &Path, args: &str, out_path: &Path) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(fixture.join("template"))
+        .arg(out_path)
+        .arg("--dry-run")
+        .args(args.lines().filter(|l| !l.is_empty()))
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}: scaf --dry-run failed: {}",
+        fixture.display(),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn check_fixture(fixture: &Path) {
+    let args = std::fs::read_to_string(fixture.join("args")).unwrap();
+    let out = TempDir::new().unwrap();
+    let out_path = out.path().join("out");
+
+    assert_eq!(
+        dry_run(fixture, &args, &out_path),
+        dry_run(fixture, &args, &out_path),
+        "{}: dry runs printed different things",
+        fixture.display()
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(fixture.join("template"))
+        .arg(&out_path)
+        .args(["--yes", "--quiet"])
+        .args(args.lines().filter(|l| !l.is_empty()))
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}: scaf failed: {}",
+        fixture.display(),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let actual = read_tree(&out_path);
+    let expected = read_tree(&fixture.join("expected"));
+
+    let actual_paths = actual.keys().collect::<Vec<_>>();
+    let expected_paths = expected.keys().collect::<Vec<_>>();
+    assert_eq!(
+        actual_paths,
+        expected_paths,
+        "{}: different files",
+        fixture.display()
+    );
+
+    for (path, contents) in &expected {
+        assert!(
+            &actual[path] == contents,
+            "{}: {} is different:\n{}",
+            fixture.display(),
+            path.display(),
+            String::from_utf8_lossy(&actual[path])
+        );
+    }
+}
+
+#[test]
+fn fixtures() {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let mut checked = 0;
+
+    for entry in std::fs::read_dir(fixtures).unwrap() {
+        let fixture = entry.unwrap().path();
+        if fixture.is_dir() {
+            check_fixture(&fixture);
+            checked += 1;
+        }
+    }
+
+    assert!(checked > 0, "no fixtures found");
+}
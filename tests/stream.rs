@@ -0,0 +1,40 @@
+use std::{collections::BTreeMap, io::Read, process::Command};
+
+use tempfile::TempDir;
+
+#[test]
+fn output_to_stdout_as_tar() {
+    let dir = TempDir::new().unwrap();
+    let template = dir.path();
+    std::fs::write(template.join("scaf.toml"), "[options]\na = \"A\"\n").unwrap();
+    std::fs::create_dir(template.join("src")).unwrap();
+    std::fs::write(template.join("src/main{a}.rs"), "fn main() {}\n").unwrap();
+    std::fs::write(template.join("README{!a}"), "no a\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .current_dir(template)
+        .args(["new", ".", "-", "--select", "a", "--yes"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    // nothing is written next to the template
+    assert!(!template.join("-").exists());
+
+    let mut archive = tar::Archive::new(output.stdout.as_slice());
+    let mut files = BTreeMap::new();
+    for entry in archive.entries().unwrap() {
+        let mut entry = entry.unwrap();
+        let mut contents = String::new();
+        entry.read_to_string(&mut contents).unwrap();
+        files.insert(entry.path().unwrap().to_path_buf(), contents);
+    }
+
+    assert_eq!(
+        files,
+        BTreeMap::from([("src/main.rs".into(), String::from("fn main() {}\n"))])
+    );
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use std::{path::Path, process::Command};
+
+use tempfile::TempDir;
+
+const CONFIG: &str = "[options]\na = \"A\"\n";
+const FILES: &[(&str, &str)] = &[
+    ("template/src/main.rs", "fn main() {}\n"),
+    ("template/{a}README.md", "# app\n"),
+];
+
+fn scaf_new(template: &Path) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(template)
+        .arg(template.with_file_name("out"))
+        .args(["--select", "a", "--yes", "--strip-prefix", "template"])
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn the_prefix_is_left_out_of_the_output() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+
+    let output = scaf_new(&template);
+    assert!(output.status.success(), "{:?}", output);
+    let out = dir.path().join("out");
+    assert!(out.join("src/main.rs").exists());
+    assert!(out.join("README.md").exists());
+    assert!(!out.join("template").exists());
+}
+
+#[test]
+fn files_outside_of_the_prefix_are_an_error() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    std::fs::write(template.join("LICENSE"), "").unwrap();
+
+    let output = scaf_new(&template);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("these files aren't inside of template"),
+        "{}",
+        stderr
+    );
+    assert!(stderr.contains("LICENSE"), "{}", stderr);
+    assert!(!dir.path().join("out").exists());
+}
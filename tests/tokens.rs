@@ -0,0 +1,66 @@
+use std::path::PathBuf;
+
+use scaf::{options_in_file, strip_options, Config, Dependency};
+use tempfile::TempDir;
+
+fn config() -> (TempDir, Config) {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("scaf.toml"),
+        "[options]\na = \"A\"\nb = \"B\"\nc = \"C\"\ntypescript = \"TypeScript\"\neslint = \"ESLint\"\n",
+    )
+    .unwrap();
+    let config = Config::from_base(dir.path(), "scaf.toml").unwrap();
+    (dir, config)
+}
+
+fn options(names: &[&str]) -> Vec<Dependency> {
+    names
+        .iter()
+        .map(|n| Dependency::Option(String::from(*n)))
+        .collect()
+}
+
+#[test]
+fn spaces_around_option_names_are_trimmed() {
+    let (_dir, config) = config();
+    assert_eq!(
+        options_in_file("{typescript, eslint}.eslintrc", &config),
+        options(&["eslint", "typescript"])
+    );
+    assert_eq!(
+        options_in_file("{ typescript }index.ts", &config),
+        options(&["typescript"])
+    );
+}
+
+#[test]
+fn tokens_next_to_each_other_are_separate() {
+    let (_dir, config) = config();
+    assert_eq!(
+        options_in_file("{a}_{b}.txt", &config),
+        options(&["a", "b"])
+    );
+}
+
+#[test]
+fn every_brace_group_adds_options_and_gets_stripped() {
+    let (_dir, config) = config();
+    let path = "{a}/src{b,c}/component.{typescript}x";
+    assert_eq!(
+        options_in_file(path, &config),
+        options(&["a", "b", "c", "typescript"])
+    );
+    assert_eq!(
+        strip_options(path, &config),
+        PathBuf::from("src/component.x")
+    );
+}
+
+#[test]
+fn the_same_option_twice_is_one_dependency() {
+    let (_dir, config) = config();
+    let path = "{typescript}/component.{typescript}x";
+    assert_eq!(options_in_file(path, &config), options(&["typescript"]));
+    assert_eq!(strip_options(path, &config), PathBuf::from("component.x"));
+}
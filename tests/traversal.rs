@@ -0,0 +1,59 @@
+mod common;
+
+use std::collections::HashMap;
+
+use scaf::{error::ScafError, scaffold, Selections};
+use tempfile::TempDir;
+
+const CONFIG: &str = "[options]\na = \"A\"\n\n[variables.dir]\nprompt = \"Directory\"\n";
+/// in a `{dir}` directory, so the variable decides where the file goes
+const FILES: &[(&str, &str)] = &[("{dir}/file.txt", "hi\n")];
+
+fn with_dir(dir: &str) -> Selections {
+    Selections {
+        options: vec![],
+        variables: HashMap::from([(String::from("dir"), String::from(dir))]),
+    }
+}
+
+#[test]
+fn parent_dirs_in_variables() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let out = dir.path().join("out");
+
+    let result = scaffold(&template, &out, &with_dir("../../escaped"));
+    match result {
+        Err(ScafError::OutsideOutput { paths, .. }) => assert_eq!(paths.len(), 1),
+        other => panic!("expected an escaping path, got {:?}", other),
+    }
+    assert!(!dir.path().join("escaped").exists());
+    assert!(!dir.path().parent().unwrap().join("escaped").exists());
+}
+
+#[test]
+fn parent_dirs_that_stay_inside() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let out = dir.path().join("out");
+
+    let written = scaffold(&template, &out, &with_dir("a/../b")).unwrap();
+    assert_eq!(written.len(), 1);
+    assert!(out.join("b/file.txt").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn symlinks_out_of_the_output() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let out = dir.path().join("out");
+    let elsewhere = dir.path().join("elsewhere");
+    std::fs::create_dir_all(&out).unwrap();
+    std::fs::create_dir_all(&elsewhere).unwrap();
+    std::os::unix::fs::symlink(&elsewhere, out.join("link")).unwrap();
+
+    let result = scaffold(&template, &out, &with_dir("link"));
+    assert!(matches!(result, Err(ScafError::OutsideOutput { .. })));
+    assert!(!elsewhere.join("file.txt").exists());
+}
@@ -0,0 +1,31 @@
+use std::process::Command;
+
+use tempfile::TempDir;
+
+#[test]
+fn prints_a_tree_of_written_files() {
+    let dir = TempDir::new().unwrap();
+    let template = dir.path().join("template");
+    std::fs::create_dir_all(template.join("src/bin")).unwrap();
+    std::fs::write(template.join("scaf.toml"), "[options]\na = \"A\"\n").unwrap();
+    std::fs::write(template.join("Cargo.toml"), "").unwrap();
+    std::fs::write(template.join("src/lib.rs"), "").unwrap();
+    std::fs::write(template.join("src/bin/main.rs"), "").unwrap();
+
+    let out = dir.path().join("out");
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(&template)
+        .arg(&out)
+        .args(["--select", "a", "--yes"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let expected = format!(
+        "{}\n├── Cargo.toml\n└── src/\n    ├── bin/\n    │   └── main.rs\n    └── lib.rs\n",
+        out.display()
+    );
+    assert!(stdout.starts_with(&expected), "{}", stdout);
+}
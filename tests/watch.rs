@@ -0,0 +1,143 @@
+mod common;
+
+use std::{
+    path::Path,
+    process::{Child, Command, Stdio},
+    time::{Duration, Instant},
+};
+
+use tempfile::TempDir;
+
+/// waits for `path` to have `contents`, since rebuilds happen in the background
+fn wait_for(path: &Path, contents: &str) -> bool {
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_secs(10) {
+        if std::fs::read_to_string(path).is_ok_and(|c| c == contents) {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    false
+}
+
+const CONFIG: &str = "[options]\na = \"A\"\n\n[variables.name]\nprompt = \"Name\"\n";
+/// `a.txt` needs the `a` option
+const FILES: &[(&str, &str)] = &[("{a}a.txt", "hi {{name}}")];
+
+fn watch(template: &Path, out: &Path, select: &str) -> Child {
+    Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg(template)
+        .arg(out)
+        .args(["--select", select, "--set", "name=app", "--yes", "--watch"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap()
+}
+
+#[test]
+fn rebuilds_when_the_template_changes() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let out = dir.path().join("out");
+    let mut child = watch(&template, &out, "a");
+
+    let first = wait_for(&out.join("a.txt"), "hi app");
+    // the watcher is set up before the first run, so this change gets picked up
+    std::fs::write(template.join("{a}a.txt"), "bye {{name}}").unwrap();
+    let rebuilt = wait_for(&out.join("a.txt"), "bye app");
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    assert!(first, "first run didn't scaffold");
+    assert!(rebuilt, "didn't rebuild after the template changed");
+}
+
+#[test]
+fn needs_a_template_on_disk() {
+    let dir = TempDir::new().unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_scaf"))
+        .arg("new")
+        .arg("https://example.com/template.git")
+        .arg(dir.path().join("out"))
+        .arg("--watch")
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("only works with templates that are on disk"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn writes_over_an_existing_output() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let out = dir.path().join("out");
+    std::fs::create_dir(&out).unwrap();
+    std::fs::write(out.join("a.txt"), "old").unwrap();
+    std::fs::write(out.join("notes.txt"), "mine").unwrap();
+
+    let mut child = watch(&template, &out, "a");
+    let first = wait_for(&out.join("a.txt"), "hi app");
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    assert!(first, "first run didn't write over the output");
+}
+
+#[test]
+fn rebuilds_after_a_first_run_with_nothing_to_scaffold() {
+    let dir = TempDir::new().unwrap();
+    let template = common::template(dir.path(), CONFIG, FILES);
+    let out = dir.path().join("out");
+
+    let mut child = watch(&template, &out, "");
+    // there's no way to tell when the first run is done, but the watcher is up before it starts
+    std::thread::sleep(Duration::from_millis(500));
+    std::fs::write(template.join("b.txt"), "b {{name}}").unwrap();
+    let rebuilt = wait_for(&out.join("b.txt"), "b app");
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    assert!(rebuilt, "didn't rebuild without a lock file");
+    assert!(!out.join("a.txt").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn rebuilds_keep_transforming_paths() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = TempDir::new().unwrap();
+    let template = common::template(
+        dir.path(),
+        "path_transform = \"transform.sh\"\n\n[options]\na = \"A\"\n\n[variables.name]\nprompt = \"Name\"\n",
+        &[
+            ("transform.sh", "#!/bin/sh\nread path\necho \"moved/$path\"\n"),
+            ("{a}a.txt", "hi {{name}}"),
+        ],
+    );
+    std::fs::set_permissions(
+        template.join("transform.sh"),
+        std::fs::Permissions::from_mode(0o755),
+    )
+    .unwrap();
+    let out = dir.path().join("out");
+    let mut child = watch(&template, &out, "a");
+
+    let first = wait_for(&out.join("moved/a.txt"), "hi app");
+    std::fs::write(template.join("{a}a.txt"), "bye {{name}}").unwrap();
+    let rebuilt = wait_for(&out.join("moved/a.txt"), "bye app");
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    assert!(first, "first run didn't scaffold");
+    assert!(rebuilt, "didn't rebuild into the transformed path");
+    assert!(!out.join("a.txt").exists());
+}